@@ -0,0 +1,130 @@
+// Streaming near-duplicate collapsing, modeled on itertools' `coalesce`: each item is
+// compared against everything already yielded (not just its immediate predecessor), and
+// only the first-seen representative of each similarity cluster passes through.
+
+use crate::simhasher::{check_narrow_width, Err as SimHasherErr, SimHasher};
+use crate::tree::HashTree;
+
+pub trait SimHashDedupeExt: Iterator + Sized {
+    /// Lazily drops items whose SimHash (computed via `mapper`) is within `max_diff`
+    /// Hamming distance of any previously yielded item's SimHash. Unlike `group_texts`,
+    /// which only runs over a fully materialized batch, this pulls from the upstream
+    /// iterator one item at a time: each `next()` call advances `self` until it finds a
+    /// non-duplicate (or the upstream iterator is exhausted).
+    ///
+    /// `seen` only indexes the low 64 bits of each hash (`HashTree`'s default `WORDS = 1`),
+    /// so `hasher` must be built with the default 64-bit width -- see [`check_narrow_width`].
+    fn simhash_dedupe<F, Q>(
+        self,
+        hasher: SimHasher,
+        max_diff: u8,
+        mapper: F,
+    ) -> Result<SimHashDedupeFilter<Self, F, Q>, SimHasherErr>
+    where
+        F: Fn(&Self::Item) -> Q,
+        Q: AsRef<str>,
+    {
+        SimHashDedupeFilter::new(self, hasher, max_diff, mapper)
+    }
+}
+
+impl<I: Iterator> SimHashDedupeExt for I {}
+
+pub struct SimHashDedupeFilter<I, F, Q> {
+    iter: I,
+    hasher: SimHasher,
+    max_diff: u8,
+    seen: HashTree<()>,
+    mapper: F,
+    _marker: std::marker::PhantomData<Q>,
+}
+
+impl<I, F, Q> SimHashDedupeFilter<I, F, Q>
+where
+    I: Iterator,
+    F: Fn(&I::Item) -> Q,
+    Q: AsRef<str>,
+{
+    pub fn new(iter: I, hasher: SimHasher, max_diff: u8, mapper: F) -> Result<Self, SimHasherErr> {
+        check_narrow_width(hasher.width())?;
+        Ok(Self {
+            iter,
+            hasher,
+            max_diff,
+            seen: HashTree::new(),
+            mapper,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<I, F, Q> Iterator for SimHashDedupeFilter<I, F, Q>
+where
+    I: Iterator,
+    F: Fn(&I::Item) -> Q,
+    Q: AsRef<str>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iter.next()?;
+            let hash = self.hasher.hash((self.mapper)(&item).as_ref());
+            if self.seen.contains([hash], self.max_diff).is_some() {
+                continue;
+            }
+            self.seen.add([hash], ());
+            return Some(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::HashMethod;
+    use crate::feature::FeatureType;
+
+    #[test]
+    fn test_simhash_dedupe() {
+        let hasher = SimHasher::new(HashMethod::XXHash, FeatureType::Words, 1).unwrap();
+        let texts = vec![
+            "the quick brown fox",
+            "the quick brown fox!",
+            "completely different sentence",
+            "the quick brown fox.",
+        ];
+
+        let deduped: Vec<&str> = texts
+            .into_iter()
+            .simhash_dedupe(hasher, 3, |s: &&str| *s)
+            .unwrap()
+            .collect();
+
+        assert_eq!(deduped, vec!["the quick brown fox", "completely different sentence"]);
+    }
+
+    #[test]
+    fn test_simhash_dedupe_is_lazy() {
+        let hasher = SimHasher::new(HashMethod::XXHash, FeatureType::Bytes, 2).unwrap();
+        let mut pulled = 0;
+        let texts = vec!["a", "a", "a"];
+
+        let mut iter = texts
+            .iter()
+            .inspect(|_| pulled += 1)
+            .simhash_dedupe(hasher, 0, |s: &&&str| **s)
+            .unwrap();
+        assert_eq!(iter.next(), Some(&"a"));
+        assert_eq!(pulled, 1);
+    }
+
+    #[test]
+    fn test_simhash_dedupe_rejects_wide_hasher() {
+        let hasher = SimHasher::with_width(HashMethod::XXHash, FeatureType::Bytes, 2, 128).unwrap();
+        let texts = vec!["a", "b"];
+
+        let result = texts.into_iter().simhash_dedupe(hasher, 0, |s: &&str| *s);
+        assert!(result.is_err());
+    }
+}
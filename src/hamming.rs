@@ -12,3 +12,9 @@ impl IntoU64 for u64 {
 pub fn hamming_distance<T: IntoU64, U: IntoU64>(a: T, b: U) -> u32 {
     (a.into_u64() ^ b.into_u64()).count_ones()
 }
+
+/// Hamming distance between two wide fingerprints, given as equal-length
+/// slices of 64-bit words (word 0 is the low-order word).
+pub fn hamming_distance_words(a: &[u64], b: &[u64]) -> u32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
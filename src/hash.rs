@@ -26,6 +26,13 @@ macro_rules! hash_dispatch {
     };
 }
 
+/// Zero-sized marker for a hasher resolved at compile time through [`hash_dispatch!`].
+///
+/// Every method here returns a plain `u64`: SipHash and XXHash are only ever asked for a
+/// 64-bit fingerprint. Wider (128/256-bit, ...) fingerprints are *not* produced by a wider
+/// native hash output on this trait — `SimHasher::hash_wide` instead derives the extra
+/// words from this `u64` via `widen_lane`. Giving `ShHash` an associated `Output`/`BITS`
+/// type so SipHash/XXHash emit a genuinely wider digest natively is future work.
 pub trait ShHash {
     fn hash_u8(value: u8) -> u64;
     fn hash_u16(value: u16) -> u64;
@@ -52,6 +59,67 @@ pub trait ShHash {
     }
 }
 
+/// Object-safe counterpart to [`ShHash`], for hashers picked at runtime instead of
+/// resolved at compile time through `hash_dispatch!`. `ShHash`'s methods live on a
+/// zero-sized marker type and take no `self`, which is exactly what lets `hash_dispatch!`
+/// monomorphize to the fast path with no indirection; that shape can't be boxed as a trait
+/// object, though, so plugging in a hasher chosen at runtime (a seeded hash, a caller's own
+/// callback, ...) needs the `&self` version here instead. The iterator-returning helpers
+/// are `Self: Sized` so they don't block object safety — they're only ever called on a
+/// concrete type, never through `dyn FeatureHasher`.
+pub trait FeatureHasher {
+    fn hash_u8(&self, value: u8) -> u64;
+    fn hash_u16(&self, value: u16) -> u64;
+    fn hash_bytes(&self, bytes: &[u8]) -> u64;
+    fn hash_multi(&self, source: &[u8], slices: Vec<(usize, usize)>) -> u64;
+
+    fn hashing_items_u8<'a>(&'a self, values: impl Iterator<Item = u8> + 'a) -> impl Iterator<Item = u64> + 'a
+    where
+        Self: Sized,
+    {
+        values.map(move |v| self.hash_u8(v))
+    }
+    fn hashing_items_u16<'a>(&'a self, values: impl Iterator<Item = u16> + 'a) -> impl Iterator<Item = u64> + 'a
+    where
+        Self: Sized,
+    {
+        values.map(move |v| self.hash_u16(v))
+    }
+    fn hashing_items_range<'a>(&'a self, ranges: impl Iterator<Item = (usize, usize)> + 'a, source: &'a str) -> impl Iterator<Item = u64> + 'a
+    where
+        Self: Sized,
+    {
+        let bytes = source.as_bytes();
+        ranges.map(move |(start, end)| self.hash_bytes(&bytes[start..end]))
+    }
+    fn hashing_windows<'a>(&'a self, ranges: impl Iterator<Item = Vec<(usize, usize)>> + 'a, source: &'a str) -> impl Iterator<Item = u64> + 'a
+    where
+        Self: Sized,
+    {
+        let bytes = source.as_bytes();
+        ranges.map(move |positions| self.hash_multi(bytes, positions))
+    }
+}
+
+/// Adapts a built-in [`HashMethod`] to the runtime-pluggable [`FeatureHasher`] trait, via
+/// the same `hash_dispatch!` resolution the fast compile-time path uses.
+pub struct BuiltinHasher(pub HashMethod);
+
+impl FeatureHasher for BuiltinHasher {
+    fn hash_u8(&self, value: u8) -> u64 {
+        hash_dispatch!(self.0, { <hasher_type!()>::hash_u8(value) })
+    }
+    fn hash_u16(&self, value: u16) -> u64 {
+        hash_dispatch!(self.0, { <hasher_type!()>::hash_u16(value) })
+    }
+    fn hash_bytes(&self, bytes: &[u8]) -> u64 {
+        hash_dispatch!(self.0, { <hasher_type!()>::hash_bytes(bytes) })
+    }
+    fn hash_multi(&self, source: &[u8], slices: Vec<(usize, usize)>) -> u64 {
+        hash_dispatch!(self.0, { <hasher_type!()>::hash_multi(source, slices) })
+    }
+}
+
 pub fn sip_hash_fn<'a, U: AsRef<[u8]> + 'a + ?Sized, T: Iterator<Item=&'a U>>(vals: T) -> u64 {
     let mut hasher = SipHasher::new();
     for val in vals {
@@ -147,6 +215,14 @@ mod tests {
         assert_eq!(v1, v2);
     }
 
+    #[test]
+    fn test_builtin_feature_hasher_matches_sh_hash() {
+        let hasher = BuiltinHasher(HashMethod::SipHash);
+        assert_eq!(hasher.hash_bytes(b"hello"), sip_::Hasher::hash_bytes(b"hello"));
+        assert_eq!(hasher.hash_u8(42), sip_::Hasher::hash_u8(42));
+        assert_eq!(hasher.hash_u16(1000), sip_::Hasher::hash_u16(1000));
+    }
+
     #[test]
     fn test_pairs() {
         use crate::feature::Features;
@@ -67,162 +67,3 @@ where
 
 
 
-
-// impl<T: IntoU64> Shr<u64> for T{
-//     type Output = (SimHash, u64);
-
-//     #[inline]
-//     fn shr(self, rhs: usize) -> Self::Output {
-//         let mask = (1 << rhs) - 1;
-//         (SimHash(self.0 >> rhs), self.0 & mask)
-//     }
-// }
-
-// struct DistanceMeasure<T: Copy + Debug + Default> {
-//     tree: HashTree<T>,
-//     identity_map: HashMap<u64, T>
-// }
-
-// impl <T: Copy + Debug + Default> DistanceMeasure<T> {
-//     pub fn new() -> Self {
-//         Self {
-//             tree: HashTree::new(),
-//             identity_map: HashMap::new()
-//         }
-//     }
-
-//     pub fn add(&mut self, hash: SimHash, value: T) {
-//         self.tree.add(hash, value);
-//         self.identity_map.insert(hash.0, value);
-//     }
-
-//     pub fn add_identity(&mut self, hash: SimHash, value: T) {
-//         self.identity_map.insert(hash.0, value);
-//     }
-
-//     pub fn contains(&self, hash: SimHash, max_diff: usize) -> Option<T> {
-//         match self.identity_map.get(&hash.0) {
-//             Some(val) => return Some(*val),
-//             None => {}
-//         }
-//         self.tree.contains(hash, max_diff)
-//     }
-
-// }
-
-
-// pub fn deduplicate_texts<T: SimHashable>(texts: &[T], max_diff: usize) -> Vec<Option<usize>> {
-//     let mut measurer = DistanceMeasure::<usize>::new();
-//     let mut results = Vec::with_capacity(texts.len());
-
-//     for (idx, text) in texts.iter().enumerate() {
-//         let hash = text.simhash();
-//         match measurer.contains(hash, max_diff) {
-//             Some(val) => {
-//                 results.push(Some(val));
-//                 measurer.add_identity(hash, val);
-//             },
-//             None => {
-//                 results.push(None);
-//                 measurer.add(hash, idx);
-//             }
-//         }
-//     }
-//     results
-// }
-
-
-// pub fn group_texts<T: SimHashable>(texts: &[T], max_diff: usize) -> HashMap<usize, Vec<usize>> {
-//     let mut measurer = DistanceMeasure::<usize>::new();
-//     let mut groups = HashMap::<usize, Vec<usize>>::with_capacity(texts.len());
-
-//     for (idx, text) in texts.iter().enumerate() {
-//         let hash = text.simhash();
-//         match measurer.contains(hash, max_diff) {
-//             Some(val) => {
-//                 groups.get_mut(&val).unwrap().push(idx);
-//             }
-//             None => {
-//                 groups.insert(idx, vec![idx]);
-//                 measurer.add(hash, idx);
-//             }
-//         }
-//     }
-//     groups
-// }
-
-
-
-
-// pub trait SimHashDedupeExt: Iterator
-// {
-//     fn deduplicate<F, Q>(self, max_diff: usize, mapper: F) -> SimHashDedupeFilter<Self, F, Q>
-//     where 
-//         F: Fn(&Self::Item) -> Q,
-//         Q: SimHashable,
-//         Self: Sized
-//     {
-//         SimHashDedupeFilter::<Self, F, Q>::new(self, max_diff, mapper)
-//     }
-// }
-
-
-// impl<I> SimHashDedupeExt for I where I: Iterator {}
-
-// pub struct SimHashDedupeFilter<I, F, U>
-// where 
-//     I: Iterator,
-//     F: Fn(&I::Item) -> U,
-//     U: SimHashable
-// {
-//     iter: I,
-//     max_diff: usize,
-//     measurer: DistanceMeasure<usize>,
-//     mapper: F
-// }
-
-// impl<I, F, U> SimHashDedupeFilter<I, F, U>
-// where 
-//     I: Iterator,
-//     F: Fn(&I::Item) -> U,
-//     U: SimHashable
-// {
-//     pub fn new(iter: I, max_diff: usize, mapper: F) -> Self {
-//         Self {
-//             iter,
-//             max_diff,
-//             measurer: DistanceMeasure::new(),
-//             mapper
-//         }
-//     }
-// }
-
-
-// impl<I, F, U> Iterator for SimHashDedupeFilter<I, F, U>
-// where
-//     I: Iterator,
-//     I::Item: Debug,
-//     F: Fn(&I::Item) -> U,
-//     U: SimHashable,
-// {
-//     type Item = I::Item;
-
-//     fn next(&mut self) -> Option<Self::Item> {
-//         loop {
-//             match self.iter.next() {
-//                 Some(item) => {
-//                     let hash = (self.mapper)(&item).simhash();
-//                     match self.measurer.contains(hash, self.max_diff) {
-//                         Some(_) => {}
-//                         None => {
-//                             self.measurer.add(hash, 0);
-//                             return Some(item);
-//                         }
-//                     }
-//                 }
-//                 None => return None,
-//             }
-//         }
-        
-//     }
-// }
\ No newline at end of file
@@ -8,10 +8,12 @@ mod window;
 mod simhasher;
 mod tree;
 mod map;
+mod dedupe;
 
 pub use simhasher::SimHasher;
 pub use feature::FeatureType;
 pub use hash::HashMethod;
+pub use dedupe::SimHashDedupeExt;
 
 
 #[pymodule]
@@ -19,7 +21,7 @@ mod simhash {
     use std::borrow::Cow;
 
     use pyo3::prelude::*;
-    use pyo3::types::{PyList, PyString};
+    use pyo3::types::{PyBytes, PyList, PyString};
 
     #[pymodule_export]
     use crate::feature::FeatureType;
@@ -30,8 +32,9 @@ mod simhash {
     #[pyclass]
     #[derive(Clone)]
     struct SimHash{
-        #[pyo3(get, set)]
-        value: u64
+        // Little-endian 64-bit words; `words[0]` is the low-order word. Always
+        // non-empty; has more than one entry only for wide (128/256-bit, ...) fingerprints.
+        words: Vec<u64>
     }
 
     #[pymethods]
@@ -39,108 +42,365 @@ mod simhash {
 
         #[staticmethod]
         fn from_int(val: u64) -> Self {
-            SimHash { value: val }
+            SimHash { words: vec![val] }
+        }
+
+        #[getter]
+        fn value(&self) -> u64 {
+            self.words[0]
         }
 
         fn __str__(&self) -> String {
-            format!("0x{:016x}", self.value)
+            let hex: String = self.words.iter().rev().map(|w| format!("{:016x}", w)).collect();
+            format!("0x{}", hex)
         }
         fn __repr__(&self) -> String {
-            format!("<SimHash 0x{:016x}>", self.value)
+            format!("<SimHash {}>", self.__str__())
         }
         fn __hash__(&self) -> u64 {
-            self.value
+            self.words[0]
         }
 
         fn __eq__(&self, other: &SimHash) -> bool {
-            self.value == other.value
+            self.words == other.words
         }
         fn __ne__(&self, other: &SimHash) -> bool {
-            self.value != other.value
+            self.words != other.words
         }
 
         // These added just to allow containers to work nicely
         fn __lt__(&self, other: &SimHash) -> bool {
-            self.value < other.value
+            self.words.iter().rev().cmp(other.words.iter().rev()) == std::cmp::Ordering::Less
         }
         fn __le__(&self, other: &SimHash) -> bool {
-            self.value <= other.value
+            self.words.iter().rev().cmp(other.words.iter().rev()) != std::cmp::Ordering::Greater
         }
         fn __gt__(&self, other: &SimHash) -> bool {
-            self.value > other.value
+            self.words.iter().rev().cmp(other.words.iter().rev()) == std::cmp::Ordering::Greater
         }
         fn __ge__(&self, other: &SimHash) -> bool {
-            self.value >= other.value
+            self.words.iter().rev().cmp(other.words.iter().rev()) != std::cmp::Ordering::Less
         }
 
-        fn __int__(&self) -> u64 {
-            self.value
+        fn __int__(&self, py: Python) -> PyResult<Py<PyAny>> {
+            let bytes: Vec<u8> = self.words.iter().flat_map(|w| w.to_le_bytes()).collect();
+            let builtins = py.import("builtins")?;
+            builtins.getattr("int")?.call_method1("from_bytes", (bytes, "little"))?.extract()
         }
 
         fn hamming_distance(&self, other: &SimHash) -> u32 {
-            super::hamming::hamming_distance(self.value, other.value)
+            super::hamming::hamming_distance_words(&self.words, &other.words)
         }
 
         fn difference(&self, other: &SimHash) -> u32 {
-            super::hamming::hamming_distance(self.value, other.value)
+            super::hamming::hamming_distance_words(&self.words, &other.words)
         }
 
     }
 
 
+    // Adapts a Python callable (`bytes -> int`) to the Rust-side `FeatureHasher` trait, so
+    // `SimHasher.with_hasher` can plug a caller's own hash function into the same
+    // feature-extraction machinery the built-in `HashMethod`s use. `hash_u8`/`hash_u16`
+    // go through the same callable as `hash_bytes`, on the little-endian bytes of the
+    // value, rather than asking Python code to implement four separate methods.
+    //
+    // `FeatureHasher::hash_*` are infallible, so a callback that raises or returns something
+    // that doesn't fit in a `u64` can't bubble its `PyErr` straight out of `call` -- it's
+    // stashed in `error` instead (shared with the owning `SimHasher` pyclass) and 0 is
+    // returned as a harmless placeholder; the caller drains `error` once back at a `PyResult`
+    // boundary, the same way `group_texts`'s `reduce` callback captures its error.
+    struct PyFeatureHasher {
+        callback: Py<PyAny>,
+        error: std::sync::Arc<std::sync::Mutex<Option<PyErr>>>,
+    }
+
+    impl PyFeatureHasher {
+        fn call(&self, bytes: &[u8]) -> u64 {
+            Python::with_gil(|py| {
+                match self
+                    .callback
+                    .call1(py, (PyBytes::new(py, bytes),))
+                    .and_then(|v| v.extract::<u64>(py))
+                {
+                    Ok(value) => value,
+                    Err(e) => {
+                        let mut error = self.error.lock().unwrap();
+                        if error.is_none() {
+                            *error = Some(e);
+                        }
+                        0
+                    }
+                }
+            })
+        }
+    }
+
+    impl crate::hash::FeatureHasher for PyFeatureHasher {
+        fn hash_u8(&self, value: u8) -> u64 {
+            self.call(&[value])
+        }
+        fn hash_u16(&self, value: u16) -> u64 {
+            self.call(&value.to_le_bytes())
+        }
+        fn hash_bytes(&self, bytes: &[u8]) -> u64 {
+            self.call(bytes)
+        }
+        fn hash_multi(&self, source: &[u8], slices: Vec<(usize, usize)>) -> u64 {
+            let mut buf = Vec::new();
+            for (start, end) in slices {
+                buf.extend_from_slice(&source[start..end]);
+            }
+            self.call(&buf)
+        }
+    }
+
     #[pyclass]
     struct SimHasher {
-        hasher: crate::simhasher::SimHasher
+        hasher: crate::simhasher::SimHasher,
+        // Only populated (and ever written to) by a `PyFeatureHasher` built via
+        // `with_hasher`; `new`'s built-in hashers never touch it.
+        py_error: std::sync::Arc<std::sync::Mutex<Option<PyErr>>>,
     }
+    impl SimHasher {
+        // Drains any error a `PyFeatureHasher` callback stashed while computing a hash, so
+        // it surfaces as a clean `PyErr` at the `PyResult` boundary of whichever method just
+        // called into the hasher, instead of staying buried in `py_error`.
+        fn take_py_error(&self) -> Option<PyErr> {
+            self.py_error.lock().unwrap().take()
+        }
+    }
+
     #[pymethods]
     impl SimHasher {
         #[new]
-        #[pyo3(signature = (hash_method=HashMethod::XXHash, features=FeatureType::Bytes, n=2 ))]
-        fn new(hash_method: HashMethod, features: FeatureType, n: usize) -> PyResult<Self> {
-            let hasher = crate::simhasher::SimHasher::new(hash_method, features, n) 
+        #[pyo3(signature = (hash_method=HashMethod::XXHash, features=FeatureType::Bytes, n=2, width=64 ))]
+        fn new(hash_method: HashMethod, features: FeatureType, n: usize, width: usize) -> PyResult<Self> {
+            let hasher = crate::simhasher::SimHasher::with_width(hash_method, features, n, width)
                 .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
 
 
             Ok(SimHasher {
-                hasher
+                hasher,
+                py_error: std::sync::Arc::new(std::sync::Mutex::new(None)),
             })
         }
 
-        fn hash(&self, input: &str) -> SimHash {
-            let hash_value = self.hasher.hash(input);
-            SimHash { value: hash_value }
+        /// Builds a `SimHasher` that hashes each feature by calling `callback(bytes) -> int`
+        /// instead of a built-in `hash_method`, so domain-specific hashing (a seeded hash,
+        /// a cryptographic hash, ...) can be plugged in from Python. Always produces a
+        /// 64-bit fingerprint, matching `SimHasher.with_hasher` on the Rust side.
+        ///
+        /// If `callback` raises, or returns something that isn't a non-negative int fitting
+        /// in 64 bits, the error surfaces from whichever method triggered the hashing (e.g.
+        /// `hash`, `group_texts`) instead of panicking.
+        #[staticmethod]
+        #[pyo3(signature = (callback, features=FeatureType::Bytes, n=2))]
+        fn with_hasher(callback: Py<PyAny>, features: FeatureType, n: usize) -> PyResult<Self> {
+            let py_error = std::sync::Arc::new(std::sync::Mutex::new(None));
+            let hasher = crate::simhasher::SimHasher::with_hasher(
+                PyFeatureHasher { callback, error: py_error.clone() },
+                features,
+                n,
+            )
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            Ok(SimHasher { hasher, py_error })
+        }
+
+        fn hash(&self, input: &str) -> PyResult<SimHash> {
+            let words = self.hasher.hash_wide(input);
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
+            Ok(SimHash { words })
         }
 
         fn features(&self, py: Python, input: &str) -> PyResult<Vec<Py<PyAny>>> {
             let features = (self.hasher.feature_extractor)(input);
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
             features.into_iter().map(|f| f.clone_into_py(py)).collect::<Result<Vec<_>, _>>()
         }
 
-        fn group_texts(&self, py: Python, texts: Bound<PyList>, max_diff: usize) -> PyResult<Vec<Vec<Py<PyAny>>>> {
-            let mut dict = crate::map::SimMap::new(
+        /// Groups `texts` into similarity clusters. With no `reduce`, returns each
+        /// cluster as a `list` of its member texts (the original behaviour). With
+        /// `reduce` (a `(acc, text) -> acc` callable) and `initial` (the seed passed to
+        /// `reduce` for each new cluster), returns each cluster's folded accumulator
+        /// instead of its raw member list.
+        #[pyo3(signature = (texts, max_diff=3, reduce=None, initial=None))]
+        fn group_texts(
+            &self,
+            py: Python,
+            texts: Bound<PyList>,
+            max_diff: usize,
+            reduce: Option<Py<PyAny>>,
+            initial: Option<Py<PyAny>>,
+        ) -> PyResult<Py<PyAny>> {
+            let Some(reduce) = reduce else {
+                let mut dict = crate::map::SimMap::new(self.hasher.clone(), max_diff as u8)
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+                let mut groups: std::collections::HashMap<usize, Vec<Py<PyAny>>> = std::collections::HashMap::new();
+
+                for text in texts.iter() {
+                    let text_val = text.extract::<String>()?;
+                    let group_val = dict.maybe_insert_close_or(text_val, || groups.len());
+                    groups.entry(*group_val).or_default().push(text.into());
+                }
+
+                if let Some(e) = self.take_py_error() {
+                    return Err(e);
+                }
+                let values: Vec<Vec<Py<PyAny>>> = groups.into_values().collect();
+                return Ok(values.into_pyobject(py).map(Py::from)?);
+            };
+
+            let pairs: Vec<(String, Py<PyAny>)> = texts
+                .iter()
+                .map(|item| Ok::<_, PyErr>((item.extract::<String>()?, item.into())))
+                .collect::<PyResult<_>>()?;
+
+            let mut error: Option<PyErr> = None;
+            let aggregates = crate::map::SimMap::<String, usize>::group_and_aggregate(
                 self.hasher.clone(),
-                max_diff as u8
-            );
-            let mut groups: std::collections::HashMap<usize, Vec<Py<PyAny>>> = std::collections::HashMap::new();
+                max_diff as u8,
+                pairs,
+                |p| p.0.clone(),
+                || initial.as_ref().map(|v| v.clone_ref(py)).unwrap_or_else(|| py.None()),
+                |acc, item: &(String, Py<PyAny>)| {
+                    if error.is_some() {
+                        return acc;
+                    }
+                    match reduce.call1(py, (acc, item.1.clone_ref(py))) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            error = Some(e);
+                            py.None()
+                        }
+                    }
+                },
+            )
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            if let Some(e) = error {
+                return Err(e);
+            }
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
 
-            for text in texts.iter() {
-                let text_val = text.extract::<String>()?;
+            let values: Vec<Py<PyAny>> = aggregates.into_values().collect();
+            Ok(values.into_pyobject(py).map(Py::from)?)
+        }
 
-                let group_val = dict.maybe_insert_close_or(text_val, || groups.len());
-                groups.entry(*group_val).or_default().push(text.into());
+        /// Returns the `k` texts in `texts` nearest to `text` by Hamming distance,
+        /// as `(distance, text)` pairs ordered nearest first.
+        fn nearest(&self, py: Python, texts: Bound<PyList>, text: &str, k: usize) -> PyResult<Vec<(u32, Py<PyAny>)>> {
+            let mut map = crate::map::SimMap::new(self.hasher.clone(), 0)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            for item in texts.iter() {
+                let s = item.extract::<String>()?;
+                map.insert(s, item.clone().into());
             }
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
+            let hash = self.hasher.hash(text);
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
+            Ok(map.query_knn(hash, k).into_iter().map(|(d, v)| (d, v.clone_ref(py))).collect())
+        }
 
-            Ok(groups.into_values().collect())
+        /// Returns every text in `texts` within `max_diff` Hamming distance of `text`,
+        /// as `(distance, text)` pairs in no particular order.
+        fn within(&self, py: Python, texts: Bound<PyList>, text: &str, max_diff: usize) -> PyResult<Vec<(u32, Py<PyAny>)>> {
+            let mut map = crate::map::SimMap::new(self.hasher.clone(), 0)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            for item in texts.iter() {
+                let s = item.extract::<String>()?;
+                map.insert(s, item.clone().into());
+            }
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
+            let hash = self.hasher.hash(text);
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
+            Ok(map.query_within(hash, max_diff as u8).into_iter().map(|(d, v)| (d, v.clone_ref(py))).collect())
+        }
+
+        /// Returns only the first-seen representative of each near-duplicate cluster in
+        /// `texts`, dropping any text whose SimHash is within `max_diff` of one already kept.
+        fn dedupe(&self, texts: Bound<PyList>, max_diff: usize) -> PyResult<Vec<Py<PyAny>>> {
+            use crate::dedupe::SimHashDedupeExt;
+
+            let pairs: Vec<(String, Py<PyAny>)> = texts
+                .iter()
+                .map(|item| Ok::<_, PyErr>((item.extract::<String>()?, item.into())))
+                .collect::<PyResult<_>>()?;
+
+            let deduped: Vec<Py<PyAny>> = pairs
+                .into_iter()
+                .simhash_dedupe(self.hasher.clone(), max_diff as u8, |p: &(String, Py<PyAny>)| p.0.clone())
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+                .map(|(_, obj)| obj)
+                .collect();
+            if let Some(e) = self.take_py_error() {
+                return Err(e);
+            }
+            Ok(deduped)
+        }
+    }
+
+    /// Incremental SimHash state for hashing byte-windowed features one chunk at a time,
+    /// so large documents don't need to be loaded into memory in full before hashing.
+    /// Only `features=FeatureType.Bytes` (the default) is supported; other feature types
+    /// raise `ValueError`, since char/grapheme/word windows can't be carried correctly
+    /// across a chunk boundary.
+    #[pyclass]
+    struct SimHashState {
+        state: crate::simhasher::SimHashState,
+    }
+    #[pymethods]
+    impl SimHashState {
+        #[new]
+        #[pyo3(signature = (hash_method=HashMethod::XXHash, features=FeatureType::Bytes, n=2, width=64 ))]
+        fn new(hash_method: HashMethod, features: FeatureType, n: usize, width: usize) -> PyResult<Self> {
+            let state = crate::simhasher::SimHashState::with_width(hash_method, features, n, width)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            Ok(SimHashState { state })
+        }
+
+        fn update(&mut self, chunk: &str) {
+            self.state.update(chunk);
+        }
+
+        fn finish(&self) -> SimHash {
+            SimHash { words: vec![self.state.finish()] }
+        }
+
+        /// Like `finish`, but returns the full-width fingerprint for states built with
+        /// `width` greater than 64.
+        fn finish_wide(&self) -> SimHash {
+            SimHash { words: self.state.finish_wide() }
+        }
+
+        /// Folds `other`'s accumulated features into this state, so fingerprints of
+        /// shards hashed independently (e.g. across processes) can be combined into one.
+        /// Both states must have been built with the same `hash_method`, `features`, `n`
+        /// and `width`.
+        fn merge(&mut self, other: &SimHashState) {
+            self.state.merge(&other.state);
         }
     }
 
     #[pyfunction]
-    #[pyo3(signature = (value, method=HashMethod::XXHash, features=FeatureType::Bytes, n=2 ))]
-    fn hash(value: &str, method: HashMethod, features: FeatureType, n: usize) -> PyResult<SimHash> {
-        let hasher = crate::simhasher::SimHasher::new(method, features, n) 
+    #[pyo3(signature = (value, method=HashMethod::XXHash, features=FeatureType::Bytes, n=2, width=64 ))]
+    fn hash(value: &str, method: HashMethod, features: FeatureType, n: usize, width: usize) -> PyResult<SimHash> {
+        let hasher = crate::simhasher::SimHasher::with_width(method, features, n, width)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let hash_value = hasher.hash(value);
-        Ok(SimHash { value: hash_value })
+        Ok(SimHash { words: hasher.hash_wide(value) })
     }
 
     #[pyfunction]
@@ -152,11 +412,27 @@ mod simhash {
         features.into_iter().map(|f| f.clone_into_py(py)).collect::<Result<Vec<_>, _>>()
     }
 
+    #[pyfunction]
+    #[pyo3(signature = (texts, max_diff=3, method=HashMethod::XXHash, features=FeatureType::Bytes, n=2, reduce=None, initial=None ))]
+    fn group_texts(
+        py: Python,
+        texts: Bound<PyList>,
+        max_diff: usize,
+        method: HashMethod,
+        features: FeatureType,
+        n: usize,
+        reduce: Option<Py<PyAny>>,
+        initial: Option<Py<PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        let hasher = SimHasher::new(method, features, n, 64)?;
+        hasher.group_texts(py, texts, max_diff, reduce, initial)
+    }
+
     #[pyfunction]
     #[pyo3(signature = (texts, max_diff=3, method=HashMethod::XXHash, features=FeatureType::Bytes, n=2 ))]
-    fn group_texts(py: Python, texts: Bound<PyList>, max_diff: usize, method: HashMethod, features: FeatureType, n: usize) -> PyResult<Vec<Vec<Py<PyAny>>>> {
-        let hasher = SimHasher::new(method, features, n)?;
-        hasher.group_texts(py, texts, max_diff)
+    fn dedupe(texts: Bound<PyList>, max_diff: usize, method: HashMethod, features: FeatureType, n: usize) -> PyResult<Vec<Py<PyAny>>> {
+        let hasher = SimHasher::new(method, features, n, 64)?;
+        hasher.dedupe(texts, max_diff)
     }
 
 
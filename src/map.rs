@@ -2,6 +2,7 @@ use std::collections::hash_map::{Entry as HashMapEntry};
 use std::collections::HashMap;
 use std::hash::Hash;
 
+use crate::simhasher::check_narrow_width;
 use crate::tree::HashTree;
 use crate::{SimHasher};
 
@@ -11,30 +12,61 @@ use crate::{SimHasher};
 /// string keys while also being searchable by the similarity of their SimHash values.
 /// It maintains a regular `HashMap` for fast exact lookups and a [`HashTree`] for
 /// approximate matching within a configurable Hamming distance.
+///
+/// `tree`/`query_index` only index the low 64 bits of each hash (`HashTree`'s default
+/// `WORDS = 1`), so `hasher` must be built with the default 64-bit width -- see
+/// [`check_narrow_width`]. `new`/`with_capacity` reject a wider hasher up front rather
+/// than silently truncating its fingerprints.
 pub struct SimMap<K: AsRef<str> + Eq + Hash, T> {
     items: HashMap<K, T>,
     tree: HashTree<T>,
+    // Separate from `tree`: indexes every inserted key's hash for `query_knn`/
+    // `query_within`, which (unlike `maybe_insert_close_or`) must never collapse two
+    // distinct keys that happen to share an exact SimHash. A leaf holds every key whose
+    // hash lands there, via `HashTree::add_multi`.
+    query_index: HashTree<Vec<K>>,
     hasher: SimHasher,
     pub max_dist: u8,
 }
 
 impl<K: AsRef<str> + Eq + Hash, T> SimMap<K, T> {
-    pub fn new(hasher: SimHasher, max_dist: u8) -> Self {
-        Self {
+    pub fn new(hasher: SimHasher, max_dist: u8) -> Result<Self, crate::simhasher::Err> {
+        check_narrow_width(hasher.width())?;
+        Ok(Self {
             items: HashMap::new(),
             tree: HashTree::new(),
+            query_index: HashTree::new(),
             hasher,
             max_dist,
-        }
+        })
     }
 
-    pub fn with_capacity(hasher: SimHasher, max_dist: u8, capacity: usize) -> Self {
-        Self {
+    pub fn with_capacity(hasher: SimHasher, max_dist: u8, capacity: usize) -> Result<Self, crate::simhasher::Err> {
+        check_narrow_width(hasher.width())?;
+        Ok(Self {
             items: HashMap::with_capacity(capacity),
             tree: HashTree::new(),
+            query_index: HashTree::new(),
             hasher,
             max_dist,
+        })
+    }
+
+    /// Inserts `key`/`value` unconditionally, indexing `key`'s hash for `query_knn` and
+    /// `query_within`. Unlike `maybe_insert_close_or`, this never collapses distinct keys
+    /// whose SimHash happens to collide exactly: every inserted key remains a candidate
+    /// match, which `query_within`'s "every match" contract depends on.
+    pub fn insert(&mut self, key: K, value: T)
+    where
+        K: Clone + AsRef<[u8]>,
+    {
+        // An exact duplicate key just overwrites the stored value; it mustn't also add a
+        // second copy of itself to `query_index`, or it would come back twice from a query.
+        if !self.items.contains_key(&key) {
+            let hash = self.hasher.hash(&key);
+            self.query_index.add_multi([hash], key.clone());
         }
+        self.items.insert(key, value);
     }
 
     pub fn len(&self) -> usize {
@@ -78,16 +110,173 @@ impl<K: AsRef<str> + Eq + Hash, T> SimMap<K, T> {
             HashMapEntry::Occupied(entry) => entry.into_mut(),
             HashMapEntry::Vacant(entry) => {
                 let hash = self.hasher.hash(entry.key());
-                let value = if let Some(value) = self.tree.contains(hash, self.max_dist) {
+                let value = if let Some(value) = self.tree.contains([hash], self.max_dist) {
                     value.clone()
                 } else {
                     let value = f();
-                    self.tree.add(hash, value.clone());
+                    self.tree.add([hash], value.clone());
                     value
                 };
                 entry.insert(value)
             }
         }
     }
-    
-}
\ No newline at end of file
+
+    /// Returns the `k` stored items closest to `hash` by Hamming distance, nearest first.
+    ///
+    /// Delegates to [`HashTree::nearest`] against `query_index`, which prunes branches
+    /// that can't improve on the heap's current k-th-best distance rather than walking
+    /// every stored item, and uses each key's hash as cached at `insert` time rather than
+    /// re-hashing it here. A leaf whose hash exactly collided with another key's holds more
+    /// than one key; those all count as one tree position, so the `k` leaves `nearest`
+    /// returns can hold more than `k` keys between them. The flattened list is truncated
+    /// to `k`, so the result never holds more than `k` entries.
+    pub fn query_knn(&self, hash: u64, k: usize) -> Vec<(u32, &T)>
+    where
+        K: AsRef<[u8]>,
+    {
+        if k == 0 {
+            return Vec::new();
+        }
+        self.query_index
+            .nearest([hash], k)
+            .into_iter()
+            .flat_map(|(d, keys)| keys.iter().map(move |key| (d as u32, self.lookup(key))))
+            .take(k)
+            .collect()
+    }
+
+    /// Returns every stored item within `max_dist` Hamming distance of `hash`, unordered.
+    ///
+    /// Delegates to [`HashTree::matches`] against `query_index`, so every key within budget
+    /// is found via pruned tree descent rather than a full scan, and no match is dropped
+    /// even if its key's hash exactly collides with another stored key's.
+    pub fn query_within(&self, hash: u64, max_dist: u8) -> Vec<(u32, &T)>
+    where
+        K: AsRef<[u8]>,
+    {
+        self.query_index
+            .matches([hash], max_dist)
+            .into_iter()
+            .flat_map(|(d, keys)| keys.iter().map(move |key| (d as u32, self.lookup(key))))
+            .collect()
+    }
+
+    // `query_index`'s leaves only ever hold keys inserted via `insert`, which always
+    // inserts the same key into `items` first, so the lookup can't miss.
+    fn lookup(&self, key: &K) -> &T {
+        self.items.get(key).expect("query_index key missing from items")
+    }
+}
+
+impl<K: AsRef<str> + AsRef<[u8]> + Eq + Hash> SimMap<K, usize> {
+    /// Groups `items` into similarity clusters exactly as `group_texts` does (via
+    /// [`SimMap::maybe_insert_close_or`]), but folds each item into a per-cluster
+    /// accumulator instead of collecting every member. Modeled on itertools'
+    /// `grouping_map`: `key_of` assigns each item its clustering key, `init` seeds a
+    /// fresh accumulator the first time a cluster is seen, and `fold` threads the running
+    /// accumulator through every member as it's assigned to that cluster.
+    pub fn group_and_aggregate<Item, Acc>(
+        hasher: SimHasher,
+        max_dist: u8,
+        items: impl IntoIterator<Item = Item>,
+        key_of: impl Fn(&Item) -> K,
+        init: impl Fn() -> Acc,
+        mut fold: impl FnMut(Acc, &Item) -> Acc,
+    ) -> Result<HashMap<usize, Acc>, crate::simhasher::Err> {
+        let mut map: SimMap<K, usize> = SimMap::new(hasher, max_dist)?;
+        let mut aggregates: HashMap<usize, Acc> = HashMap::new();
+
+        for item in items {
+            let key = key_of(&item);
+            let group_id = *map.maybe_insert_close_or(key, || aggregates.len());
+            let acc = aggregates.remove(&group_id).unwrap_or_else(&init);
+            aggregates.insert(group_id, fold(acc, &item));
+        }
+
+        Ok(aggregates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::FeatureType;
+    use crate::hash::FeatureHasher;
+
+    // Hashes every feature to the same value, so every text indexed with it collides on
+    // an identical SimHash — used below to exercise the exact-collision path without
+    // needing to hunt for two real strings that happen to collide.
+    struct ConstantHasher(u64);
+    impl FeatureHasher for ConstantHasher {
+        fn hash_u8(&self, _value: u8) -> u64 {
+            self.0
+        }
+        fn hash_u16(&self, _value: u16) -> u64 {
+            self.0
+        }
+        fn hash_bytes(&self, _bytes: &[u8]) -> u64 {
+            self.0
+        }
+        fn hash_multi(&self, _source: &[u8], _slices: Vec<(usize, usize)>) -> u64 {
+            self.0
+        }
+    }
+
+    fn colliding_map() -> SimMap<String, String> {
+        let hasher = SimHasher::with_hasher(ConstantHasher(42), FeatureType::Bytes, 1).unwrap();
+        let mut map: SimMap<String, String> = SimMap::new(hasher, 0).unwrap();
+        map.insert("alpha".to_string(), "alpha".to_string());
+        map.insert("beta".to_string(), "beta".to_string());
+        map
+    }
+
+    #[test]
+    fn test_query_within_returns_every_exact_hash_collision() {
+        let map = colliding_map();
+        let hash = map.hasher().hash("anything");
+
+        let mut results: Vec<String> = map.query_within(hash, 0).into_iter().map(|(_, v)| v.clone()).collect();
+        results.sort();
+        assert_eq!(results, vec!["alpha".to_string(), "beta".to_string()]);
+    }
+
+    #[test]
+    fn test_query_knn_returns_every_exact_hash_collision() {
+        let map = colliding_map();
+        let hash = map.hasher().hash("anything");
+
+        let mut results: Vec<String> = map.query_knn(hash, 2).into_iter().map(|(_, v)| v.clone()).collect();
+        results.sort();
+        assert_eq!(results, vec!["alpha".to_string(), "beta".to_string()]);
+    }
+
+    #[test]
+    fn test_insert_overwrites_duplicate_key_without_duplicating_index_entry() {
+        let mut map = colliding_map();
+        map.insert("alpha".to_string(), "alpha-v2".to_string());
+
+        let hash = map.hasher().hash("anything");
+        let results = map.query_within(hash, 0);
+        assert_eq!(results.len(), 2);
+        assert_eq!(map.get(&"alpha".to_string()), Some(&"alpha-v2".to_string()));
+    }
+
+    #[test]
+    fn test_query_knn_respects_k_without_collisions() {
+        let hasher = crate::SimHasher::new(crate::hash::HashMethod::XXHash, FeatureType::Bytes, 2).unwrap();
+        let mut map: SimMap<String, String> = SimMap::new(hasher, 0).unwrap();
+        for word in ["apple", "banana", "cherry", "date"] {
+            map.insert(word.to_string(), word.to_string());
+        }
+        let hash = map.hasher().hash("apple");
+        assert_eq!(map.query_knn(hash, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_new_rejects_wide_hasher() {
+        let hasher = crate::SimHasher::with_width(crate::hash::HashMethod::XXHash, FeatureType::Bytes, 2, 128).unwrap();
+        let map: Result<SimMap<String, String>, _> = SimMap::new(hasher, 0);
+        assert!(map.is_err());
+    }
+}
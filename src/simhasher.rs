@@ -1,10 +1,11 @@
 use std::fmt::Display;
+use std::sync::Arc;
 
 use pyo3::{IntoPyObject, Py, PyAny, PyErr, PyObject, PyResult};
 
 use crate::{
     feature::{FeatureType, Features},
-    hash::{HashMethod, ShHash},
+    hash::{FeatureHasher, HashMethod, ShHash},
     hash_dispatch,
     util::{PairToU16Ext, SequentialToRange, window_range},
     window::{PairIterExt, SlidingWindowIterExt},
@@ -19,16 +20,49 @@ impl Display for InvalidWindowSize {
     }
 }
 
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct InvalidWidth(&'static str);
+
+impl Display for InvalidWidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid fingerprint width: {}", self.0)
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct UnsupportedFeatureType(&'static str);
+
+impl Display for UnsupportedFeatureType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsupported feature type: {}", self.0)
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct UnsupportedWidth(&'static str);
+
+impl Display for UnsupportedWidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsupported fingerprint width: {}", self.0)
+    }
+}
+
 
 #[derive(Debug)]
 pub enum Err {
-    InvalidWindowSize(InvalidWindowSize)
+    InvalidWindowSize(InvalidWindowSize),
+    InvalidWidth(InvalidWidth),
+    UnsupportedFeatureType(UnsupportedFeatureType),
+    UnsupportedWidth(UnsupportedWidth),
 }
 
 impl Display for Err {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Err::InvalidWindowSize(e) => write!(f, "{}", e),
+            Err::InvalidWidth(e) => write!(f, "{}", e),
+            Err::UnsupportedFeatureType(e) => write!(f, "{}", e),
+            Err::UnsupportedWidth(e) => write!(f, "{}", e),
         }
     }
 }
@@ -39,34 +73,78 @@ impl From<InvalidWindowSize> for Err {
     }
 }
 
+impl From<InvalidWidth> for Err {
+    fn from(e: InvalidWidth) -> Self {
+        Err::InvalidWidth(e)
+    }
+}
+
+impl From<UnsupportedFeatureType> for Err {
+    fn from(e: UnsupportedFeatureType) -> Self {
+        Err::UnsupportedFeatureType(e)
+    }
+}
+
+impl From<UnsupportedWidth> for Err {
+    fn from(e: UnsupportedWidth) -> Self {
+        Err::UnsupportedWidth(e)
+    }
+}
+
+/// The default SimHash fingerprint width, in bits. Matches the width of a single `u64`.
+pub const DEFAULT_WIDTH: usize = 64;
+
+fn check_width(width: usize) -> Result<(), InvalidWidth> {
+    if width == 0 || width % DEFAULT_WIDTH != 0 {
+        return Err(InvalidWidth("width must be a positive multiple of 64"));
+    }
+    Ok(())
+}
+
+/// Validates that a hasher built for clustering/indexing (`SimMap`, `simhash_dedupe`) has
+/// the default 64-bit width. `HashTree` (and so `SimMap`'s `tree`/`query_index` fields) is
+/// keyed on a compile-time `WORDS` const generic fixed at 1 for these callers, so a hasher
+/// built with `with_width(..., width > 64)` would have its high words silently dropped
+/// rather than indexed -- reject it up front instead.
+pub fn check_narrow_width(width: usize) -> Result<(), Err> {
+    if width != DEFAULT_WIDTH {
+        return Err(UnsupportedWidth(
+            "SimMap, group_texts, dedupe and nearest/within only support a SimHasher built with the default 64-bit width; wider fingerprints aren't indexed by HashTree here",
+        ).into());
+    }
+    Ok(())
+}
+
 fn make_simhasher(
     features: FeatureType,
     window_size: usize,
     hash_method: HashMethod,
-) -> Result<Box<dyn Fn(&str) -> u64 + Send + Sync>, Err> {
+    width: usize,
+) -> Result<Box<dyn Fn(&str) -> Vec<u64> + Send + Sync>, Err> {
     if window_size == 0 {
         return Err(InvalidWindowSize("Window size must be greater than 0").into());
     }
+    check_width(width)?;
     hash_dispatch!(hash_method, {
         match features {
             FeatureType::Bytes => match window_size {
                 1 => {
                     return Ok(Box::new(move |s: &str| {
                         let hashes = <hasher_type!()>::hashing_items_u8(s.byte_features());
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
                 2 => {
                     return Ok(Box::new(move |s: &str| {
                         let vals = s.byte_features().sliding_pairs().pair_to_u16();
                         let hashes = <hasher_type!()>::hashing_items_u16(vals);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
                 n => {
                     return Ok(Box::new(move |s: &str| {
                         let hashes = <hasher_type!()>::hashing_items_range(window_range(s.len(), n), s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
             },
@@ -75,14 +153,14 @@ fn make_simhasher(
                     return Ok(Box::new(move |s: &str| {
                         let char_indices = s.char_features().sequential_to_range();
                         let hashes = <hasher_type!()>::hashing_items_range(char_indices, s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
                 n => {
                     return Ok(Box::new(move |s: &str| {
                         let windows = s.char_features().sequential_to_range().sliding_window(n);
                         let hashes = <hasher_type!()>::hashing_windows(windows, s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
             },
@@ -91,14 +169,14 @@ fn make_simhasher(
                     return Ok(Box::new(move |s: &str| {
                         let grapheme_indices = s.grapheme_features().sequential_to_range();
                         let hashes = <hasher_type!()>::hashing_items_range(grapheme_indices, s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
                 n => {
                     return Ok(Box::new(move |s: &str| {
                         let windows = s.grapheme_features().sequential_to_range().sliding_window(n);
                         let hashes = <hasher_type!()>::hashing_windows(windows, s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
             },
@@ -107,14 +185,14 @@ fn make_simhasher(
                     return Ok(Box::new(move |s: &str| {
                         let word_indices = s.word_features();
                         let hashes = <hasher_type!()>::hashing_items_range(word_indices, s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
                 n => {
                     return Ok(Box::new(move |s: &str| {
                         let windows = s.word_features().sliding_window(n);
                         let hashes = <hasher_type!()>::hashing_windows(windows, s);
-                        simhash_impl(hashes)
+                        simhash_impl_wide(hashes, width)
                     }));
                 }
             },
@@ -123,6 +201,104 @@ fn make_simhasher(
     })
 }
 
+// Same shape as `make_simhasher`, but resolved against a runtime `FeatureHasher` trait
+// object instead of a compile-time `hash_dispatch!` arm, for `SimHasher::with_hasher`.
+// Custom hashers always produce a 64-bit fingerprint: widening relies on `widen_lane`
+// deriving extra lanes from the hash, a detail of the built-in hashers that a caller's own
+// hash function has no reason to support.
+fn make_simhasher_with(
+    hasher: Arc<dyn FeatureHasher + Send + Sync>,
+    features: FeatureType,
+    window_size: usize,
+) -> Result<Box<dyn Fn(&str) -> Vec<u64> + Send + Sync>, Err> {
+    if window_size == 0 {
+        return Err(InvalidWindowSize("Window size must be greater than 0").into());
+    }
+    match features {
+        FeatureType::Bytes => match window_size {
+            1 => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let hashes = s.byte_features().map(|b| hasher.hash_u8(b));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+            2 => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let hashes = s.byte_features().sliding_pairs().pair_to_u16().map(|v| hasher.hash_u16(v));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+            n => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let hashes = window_range(s.len(), n).map(move |(start, end)| hasher.hash_bytes(&bytes[start..end]));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+        },
+        FeatureType::Chars => match window_size {
+            1 => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let hashes = s.char_features().sequential_to_range().map(move |(start, end)| hasher.hash_bytes(&bytes[start..end]));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+            n => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let windows = s.char_features().sequential_to_range().sliding_window(n);
+                    let hashes = windows.map(move |positions| hasher.hash_multi(bytes, positions));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+        },
+        FeatureType::Graphemes => match window_size {
+            1 => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let hashes = s.grapheme_features().sequential_to_range().map(move |(start, end)| hasher.hash_bytes(&bytes[start..end]));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+            n => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let windows = s.grapheme_features().sequential_to_range().sliding_window(n);
+                    let hashes = windows.map(move |positions| hasher.hash_multi(bytes, positions));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+        },
+        FeatureType::Words => match window_size {
+            1 => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let hashes = s.word_features().map(move |(start, end)| hasher.hash_bytes(&bytes[start..end]));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+            n => {
+                let hasher = hasher.clone();
+                Ok(Box::new(move |s: &str| {
+                    let bytes = s.as_bytes();
+                    let windows = s.word_features().sliding_window(n);
+                    let hashes = windows.map(move |positions| hasher.hash_multi(bytes, positions));
+                    simhash_impl_wide(hashes, DEFAULT_WIDTH)
+                }))
+            }
+        },
+    }
+}
+
 pub trait AnyFeature{
     fn clone_into_py(&self, py: pyo3::Python) -> PyResult<Py<PyAny>>;
 }
@@ -195,11 +371,96 @@ pub fn simhash_impl(hashes: impl Iterator<Item = u64>) -> u64 {
     val
 }
 
+// Derives an extra 64-bit lane from a feature's base hash, rather than re-hashing the
+// feature bytes, so widening a fingerprint doesn't require plumbing raw bytes through
+// every `hash_dispatch!` arm. Lane 0 is always the untouched base hash, so width-64
+// output is bit-for-bit identical to `simhash_impl`.
+//
+// IMPORTANT: this makes every lane a deterministic function of `base` alone, not an
+// independent 64 bits of entropy. Two features whose base hashes collide get identical
+// bits in *every* lane, not just lane 0 — widening reduces the odds that two unrelated
+// texts share a fingerprint by chance only insofar as their base hashes already differ;
+// it does not add `width - 64` bits of independent collision resistance on top of that.
+// A correct fix would re-hash each feature's original bytes with a distinct key per
+// lane, which `simhash_impl_wide`'s callers don't currently have the bytes on hand to do
+// (see the module-level call sites in `make_simhasher`/`make_simhasher_with`).
+#[inline(always)]
+fn widen_lane(base: u64, lane: usize) -> u64 {
+    if lane == 0 {
+        return base;
+    }
+    let mut h = base ^ (lane as u64).wrapping_mul(0x9E3779B97F4A7C15);
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xBF58476D1CE4E5B9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94D049BB133111EB);
+    h ^= h >> 31;
+    h
+}
+
+/// Same algorithm as [`simhash_impl`], but produces a fingerprint of `width` bits
+/// (a positive multiple of 64) instead of a fixed `u64`. Word 0 of the result is the
+/// low-order word and is identical to `simhash_impl`'s output when `width == 64`.
+///
+/// Uses a signed per-bit accumulator (`+1` when a feature's bit is set, `-1` when clear,
+/// output bit set when the total is `> 0`) rather than `simhash_impl`'s unsigned counts
+/// compared against `total/2`. The two are equivalent for every feature count: summing
+/// `+1`/`-1` and testing `> 0` is the same as counting set bits and testing `> total/2`,
+/// with ties going to `0` either way. The signed form is used here (and in
+/// [`SimHashState`]) because it doesn't need the feature count up front, which matters
+/// once fingerprints are folded in incrementally rather than from one complete iterator.
+///
+/// Caveat: words 1.. are derived from word 0 via [`widen_lane`], a deterministic mix
+/// rather than an independent re-hash of each feature's bytes, so a `width` greater
+/// than 64 does *not* give `width` bits of independent collision resistance — see
+/// `widen_lane`'s doc comment.
+pub fn simhash_impl_wide(hashes: impl Iterator<Item = u64>, width: usize) -> Vec<u64> {
+    let lanes = width / 64;
+    let mut buckets = vec![0i64; width];
+
+    for hash in hashes {
+        for lane in 0..lanes {
+            let lane_hash = widen_lane(hash, lane);
+            for bit in 0..64 {
+                let idx = lane * 64 + bit;
+                buckets[idx] += if (lane_hash >> bit) & 1 == 1 { 1 } else { -1 };
+            }
+        }
+    }
+
+    (0..lanes)
+        .map(|lane| {
+            (0..64).fold(0u64, |acc, bit| {
+                let idx = lane * 64 + bit;
+                let bitval = (if buckets[idx] > 0 { 1 } else { 0 }) << bit;
+                acc | bitval
+            })
+        })
+        .collect()
+}
+
+// What a `SimHasher` was built from, kept around so `Clone` can rebuild the `maker`
+// closure rather than trying to clone it directly (`Box<dyn Fn...>` isn't `Clone`).
+enum HashSource {
+    Builtin(HashMethod),
+    Custom(Arc<dyn FeatureHasher + Send + Sync>),
+}
+
+impl Clone for HashSource {
+    fn clone(&self) -> Self {
+        match self {
+            HashSource::Builtin(method) => HashSource::Builtin(*method),
+            HashSource::Custom(hasher) => HashSource::Custom(hasher.clone()),
+        }
+    }
+}
+
 pub struct SimHasher {
-    hash_method: HashMethod,
+    source: HashSource,
     feature_type: FeatureType,
     window_size: usize,
-    maker: Box<dyn Fn(&str) -> u64 + Send + Sync>,
+    width: usize,
+    maker: Box<dyn Fn(&str) -> Vec<u64> + Send + Sync>,
     pub feature_extractor: Box<dyn Fn(&str) -> Vec<Box<dyn AnyFeature>> + Send + Sync>,
 }
 
@@ -209,25 +470,235 @@ impl SimHasher {
         features: FeatureType,
         window_size: usize,
     ) -> Result<Self, Err> {
-        let maker = make_simhasher(features, window_size, hash_method)?;
+        Self::with_width(hash_method, features, window_size, DEFAULT_WIDTH)
+    }
+
+    /// Like [`SimHasher::new`], but picks the fingerprint width (in bits) instead of
+    /// assuming 64. `width` must be a positive multiple of 64 (64, 128, 192, 256, ...).
+    ///
+    /// Note: bits beyond the first 64 are a deterministic derivation of the low 64 bits
+    /// (see [`widen_lane`]), not independently hashed — widening lowers the chance of an
+    /// accidental collision only to the extent the low 64 bits already differ, so it does
+    /// not buy `width` bits' worth of independent collision resistance. A hasher built with
+    /// `width > 64` is also rejected by `SimMap` and `simhash_dedupe` (see
+    /// [`check_narrow_width`]) — only [`SimHasher::hash_wide`] and [`SimHashState`] actually
+    /// consume the extra words; clustering/deduping stays on the low 64 bits only.
+    pub fn with_width(
+        hash_method: HashMethod,
+        features: FeatureType,
+        window_size: usize,
+        width: usize,
+    ) -> Result<Self, Err> {
+        let maker = make_simhasher(features, window_size, hash_method, width)?;
         let feature_extractor = make_feature_extractor(features);
         Ok(Self {
-            hash_method,
+            source: HashSource::Builtin(hash_method),
+            feature_type: features,
+            window_size,
+            width,
+            maker,
+            feature_extractor,
+        })
+    }
+
+    /// Like [`SimHasher::new`], but hashes features with a caller-supplied [`FeatureHasher`]
+    /// (a seeded hash, a cryptographic hash, ...) instead of a built-in [`HashMethod`].
+    /// Custom hashers always produce a 64-bit fingerprint; see [`make_simhasher_with`] for why.
+    pub fn with_hasher<H>(hasher: H, features: FeatureType, window_size: usize) -> Result<Self, Err>
+    where
+        H: FeatureHasher + Send + Sync + 'static,
+    {
+        let hasher: Arc<dyn FeatureHasher + Send + Sync> = Arc::new(hasher);
+        let maker = make_simhasher_with(hasher.clone(), features, window_size)?;
+        let feature_extractor = make_feature_extractor(features);
+        Ok(Self {
+            source: HashSource::Custom(hasher),
             feature_type: features,
             window_size,
+            width: DEFAULT_WIDTH,
             maker,
             feature_extractor,
         })
     }
 
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
     pub fn hash<T: AsRef<str>>(&self, text: T) -> u64 {
+        (self.maker)(text.as_ref())[0]
+    }
+
+    /// Hashes `text` into the full-width fingerprint, as little-endian 64-bit words.
+    pub fn hash_wide<T: AsRef<str>>(&self, text: T) -> Vec<u64> {
         (self.maker)(text.as_ref())
     }
 }
 
 impl Clone for SimHasher {
     fn clone(&self) -> Self {
-        SimHasher::new(self.hash_method, self.feature_type, self.window_size).unwrap()
+        match &self.source {
+            HashSource::Builtin(method) => {
+                SimHasher::with_width(*method, self.feature_type, self.window_size, self.width).unwrap()
+            }
+            HashSource::Custom(hasher) => {
+                let maker = make_simhasher_with(hasher.clone(), self.feature_type, self.window_size).unwrap();
+                SimHasher {
+                    source: HashSource::Custom(hasher.clone()),
+                    feature_type: self.feature_type,
+                    window_size: self.window_size,
+                    width: self.width,
+                    maker,
+                    feature_extractor: make_feature_extractor(self.feature_type),
+                }
+            }
+        }
+    }
+}
+
+/// Incremental counterpart to [`SimHasher::hash`] for byte-windowed fingerprints: accepts
+/// input a chunk at a time via [`SimHashState::update`] instead of requiring the whole
+/// string up front, so hashing a multi-megabyte document (or a stream) doesn't need it
+/// all resident in memory at once.
+///
+/// Only [`FeatureType::Bytes`] is supported: carrying a partial window across chunk
+/// boundaries is straightforward for raw bytes (just keep the trailing `window_size - 1`
+/// bytes), but `Chars`/`Graphemes`/`Words` windows depend on boundary detection that can
+/// itself span a chunk split, which this state does not attempt. Building a state with
+/// any other `FeatureType` is rejected up front, rather than silently producing a
+/// fingerprint that diverges from the matching `SimHasher`.
+///
+/// A signed per-bit accumulator is used instead of the unsigned bucket counts in
+/// [`simhash_impl`] so that chunks can be folded in one at a time: `buckets[i] > 0` after
+/// the last `update` is exactly equivalent to `simhash_impl`'s `count_set > total/2` test.
+pub struct SimHashState {
+    hash_method: HashMethod,
+    feature_type: FeatureType,
+    window_size: usize,
+    width: usize,
+    buckets: Vec<i64>,
+    feature_count: u64,
+    // Trailing bytes from the previous chunk that haven't yet completed a window.
+    carry: Vec<u8>,
+}
+
+fn check_feature_type(features: FeatureType) -> Result<(), UnsupportedFeatureType> {
+    match features {
+        FeatureType::Bytes => Ok(()),
+        _ => Err(UnsupportedFeatureType(
+            "SimHashState only supports FeatureType::Bytes; Chars/Graphemes/Words windows can't be carried across chunk boundaries",
+        )),
+    }
+}
+
+impl SimHashState {
+    pub fn new(hash_method: HashMethod, features: FeatureType, window_size: usize) -> Result<Self, Err> {
+        Self::with_width(hash_method, features, window_size, DEFAULT_WIDTH)
+    }
+
+    /// Like [`SimHashState::new`], but picks the fingerprint width (in bits) instead of
+    /// assuming 64. `width` must be a positive multiple of 64 (64, 128, 192, 256, ...).
+    ///
+    /// Note: as with [`SimHasher::with_width`], bits beyond the first 64 are derived
+    /// from the low 64 bits (see [`widen_lane`]) rather than independently hashed, so
+    /// they don't add independent collision resistance on top of the base 64 bits.
+    pub fn with_width(hash_method: HashMethod, features: FeatureType, window_size: usize, width: usize) -> Result<Self, Err> {
+        if window_size == 0 {
+            return Err(InvalidWindowSize("Window size must be greater than 0").into());
+        }
+        check_feature_type(features)?;
+        check_width(width)?;
+        Ok(Self {
+            hash_method,
+            feature_type: features,
+            window_size,
+            width,
+            buckets: vec![0i64; width],
+            feature_count: 0,
+            carry: Vec::new(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Folds the byte-windowed features of `chunk` into the running fingerprint. Windows
+    /// that span the boundary with the previous call are carried over correctly; a chunk
+    /// may split a multi-byte UTF-8 sequence without affecting the result, since byte
+    /// features operate on raw bytes.
+    pub fn update(&mut self, chunk: &str) {
+        let mut buf = std::mem::take(&mut self.carry);
+        buf.extend_from_slice(chunk.as_bytes());
+
+        if buf.len() >= self.window_size {
+            let limit = buf.len() - self.window_size + 1;
+            let lanes = self.width / 64;
+            hash_dispatch!(self.hash_method, {
+                for start in 0..limit {
+                    let hash = <hasher_type!()>::hash_bytes(&buf[start..start + self.window_size]);
+                    self.feature_count += 1;
+                    for lane in 0..lanes {
+                        let lane_hash = widen_lane(hash, lane);
+                        for bit in 0..64 {
+                            let idx = lane * 64 + bit;
+                            self.buckets[idx] += if (lane_hash >> bit) & 1 == 1 { 1 } else { -1 };
+                        }
+                    }
+                }
+            });
+            self.carry = buf.split_off(limit);
+        } else {
+            self.carry = buf;
+        }
+    }
+
+    /// Number of byte-windowed features folded in so far.
+    pub fn feature_count(&self) -> u64 {
+        self.feature_count
+    }
+
+    /// Applies the sign threshold to the accumulated buckets, exactly as `simhash_impl`
+    /// would for the same features seen in one batch. Can be called repeatedly; does not
+    /// consume the state, so hashing can continue with further `update` calls afterwards.
+    pub fn finish(&self) -> u64 {
+        self.finish_wide()[0]
+    }
+
+    /// Like [`SimHashState::finish`], but returns the full-width fingerprint as
+    /// little-endian 64-bit words, for states built with [`SimHashState::with_width`].
+    pub fn finish_wide(&self) -> Vec<u64> {
+        let lanes = self.width / 64;
+        (0..lanes)
+            .map(|lane| {
+                (0..64).fold(0u64, |acc, bit| {
+                    let idx = lane * 64 + bit;
+                    let bitval = (if self.buckets[idx] > 0 { 1 } else { 0 }) << bit;
+                    acc | bitval
+                })
+            })
+            .collect()
+    }
+
+    /// Folds `other`'s accumulated buckets and feature count into `self`, so shards of a
+    /// document hashed independently (e.g. by a parallel map-reduce) can be combined into
+    /// one fingerprint without re-reading any of the input. Only valid for states built
+    /// with the same `hash_method`, `window_size` and `width`; panics otherwise, since
+    /// merging buckets built from different hashes or window sizes would produce a
+    /// fingerprint that doesn't correspond to any real feature set.
+    ///
+    /// Any unfinished `carry` bytes in either state are not merged — each shard is assumed
+    /// to have already folded in all of its own features via `update`.
+    pub fn merge(&mut self, other: &SimHashState) {
+        assert_eq!(self.hash_method, other.hash_method, "cannot merge SimHashState built with different hash methods");
+        assert_eq!(self.feature_type, other.feature_type, "cannot merge SimHashState built with different feature types");
+        assert_eq!(self.window_size, other.window_size, "cannot merge SimHashState built with different window sizes");
+        assert_eq!(self.width, other.width, "cannot merge SimHashState built with different widths");
+
+        for (bucket, &other_bucket) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            *bucket += other_bucket;
+        }
+        self.feature_count += other.feature_count;
     }
 }
 
@@ -274,4 +745,139 @@ mod tests {
         assert_eq!(v_bytes, v_chars);
         assert_eq!(v_bytes, v_graph);
     }
+
+    #[test]
+    fn test_wide_hash_matches_narrow_in_low_word() {
+        let narrow = SimHasher::with_width(HashMethod::XXHash, FeatureType::Bytes, 2, 64).unwrap();
+        let wide = SimHasher::with_width(HashMethod::XXHash, FeatureType::Bytes, 2, 128).unwrap();
+
+        let text = "Hello world!";
+        let wide_hash = wide.hash_wide(text);
+        assert_eq!(wide_hash.len(), 2);
+        assert_eq!(wide_hash[0], narrow.hash(text));
+        assert_eq!(wide.hash(text), wide_hash[0]);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(7)]
+    fn test_wide_signed_accumulator_matches_narrow_unsigned(#[case] n: usize) {
+        let val = "Hello world! This text has enough features to exercise odd and even counts.";
+        let h = crate::hash::sip_::Hasher::hashing_items_range(window_range(val.len(), n), val);
+        let narrow = simhash_impl(h);
+
+        let h2 = crate::hash::sip_::Hasher::hashing_items_range(window_range(val.len(), n), val);
+        let wide = simhash_impl_wide(h2, 64);
+        assert_eq!(wide.len(), 1);
+        assert_eq!(wide[0], narrow);
+    }
+
+    // Documents the known limitation in `widen_lane`'s doc comment: every extra lane is a
+    // deterministic function of the base hash, so two features whose base hashes collide
+    // also collide in every extra lane. This is expected, not a bug to "fix" by tweaking
+    // the mix — a real fix would need to re-hash the original feature bytes per lane.
+    #[test]
+    fn test_widen_lane_is_fully_determined_by_base_hash() {
+        assert_eq!(widen_lane(42, 1), widen_lane(42, 1));
+        assert_ne!(widen_lane(42, 1), widen_lane(43, 1));
+        assert_eq!(widen_lane(42, 0), 42);
+    }
+
+    #[test]
+    fn test_invalid_width_rejected() {
+        assert!(SimHasher::with_width(HashMethod::XXHash, FeatureType::Bytes, 2, 0).is_err());
+        assert!(SimHasher::with_width(HashMethod::XXHash, FeatureType::Bytes, 2, 100).is_err());
+    }
+
+    #[rstest]
+    #[case(FeatureType::Chars)]
+    #[case(FeatureType::Graphemes)]
+    #[case(FeatureType::Words)]
+    fn test_simhash_state_rejects_non_byte_feature_types(#[case] features: FeatureType) {
+        assert!(SimHashState::new(HashMethod::XXHash, features, 2).is_err());
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(5)]
+    fn test_simhash_state_matches_batch(#[case] n: usize) {
+        let text = "The quick brown fox jumps over the lazy dog";
+        let sh = SimHasher::new(HashMethod::XXHash, FeatureType::Bytes, n).unwrap();
+        let expected = sh.hash(text);
+
+        let mut state = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, n).unwrap();
+        for chunk in [&text[0..10], &text[10..23], &text[23..]] {
+            state.update(chunk);
+        }
+        assert_eq!(state.finish(), expected);
+    }
+
+    #[test]
+    fn test_with_hasher_matches_builtin_for_same_method() {
+        let builtin = SimHasher::new(HashMethod::SipHash, FeatureType::Bytes, 2).unwrap();
+        let custom = SimHasher::with_hasher(crate::hash::BuiltinHasher(HashMethod::SipHash), FeatureType::Bytes, 2).unwrap();
+
+        let text = "Hello world!";
+        assert_eq!(builtin.hash(text), custom.hash(text));
+    }
+
+    #[test]
+    fn test_with_hasher_clone_reuses_hasher() {
+        let sh = SimHasher::with_hasher(crate::hash::BuiltinHasher(HashMethod::XXHash), FeatureType::Words, 1).unwrap();
+        let cloned = sh.clone();
+        assert_eq!(sh.hash("a quick test"), cloned.hash("a quick test"));
+    }
+
+    #[test]
+    fn test_simhash_state_single_chunk() {
+        let text = "hello world";
+        let sh = SimHasher::new(HashMethod::SipHash, FeatureType::Bytes, 2).unwrap();
+        let mut state = SimHashState::new(HashMethod::SipHash, FeatureType::Bytes, 2).unwrap();
+        state.update(text);
+        assert_eq!(state.finish(), sh.hash(text));
+    }
+
+    #[test]
+    fn test_simhash_state_wide_matches_narrow_in_low_word() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        let mut narrow = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, 2).unwrap();
+        narrow.update(text);
+
+        let mut wide = SimHashState::with_width(HashMethod::XXHash, FeatureType::Bytes, 2, 128).unwrap();
+        wide.update(text);
+
+        let wide_hash = wide.finish_wide();
+        assert_eq!(wide_hash.len(), 2);
+        assert_eq!(wide_hash[0], narrow.finish());
+    }
+
+    #[test]
+    fn test_simhash_state_merge_matches_single_pass() {
+        // Shards overlap by `window_size - 1` chars so every window in the original text
+        // (including the one spanning the split point) is covered by exactly one shard.
+        let text = "The quick brown fox jumps over the lazy dog";
+        let window_size = 3;
+        let mut whole = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, window_size).unwrap();
+        whole.update(text);
+
+        let mut shard_a = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, window_size).unwrap();
+        shard_a.update(&text[0..20]);
+        let mut shard_b = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, window_size).unwrap();
+        shard_b.update(&text[20 - (window_size - 1)..]);
+
+        shard_a.merge(&shard_b);
+        assert_eq!(shard_a.feature_count(), whole.feature_count());
+        assert_eq!(shard_a.finish(), whole.finish());
+    }
+
+    #[test]
+    #[should_panic(expected = "window sizes")]
+    fn test_simhash_state_merge_rejects_mismatched_window_size() {
+        let mut a = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, 2).unwrap();
+        let b = SimHashState::new(HashMethod::XXHash, FeatureType::Bytes, 3).unwrap();
+        a.merge(&b);
+    }
 }
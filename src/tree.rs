@@ -1,53 +1,69 @@
 // Hash Tree implementation for efficient similarity search using Hamming distance
 //
-// This module implements a 16-way branching tree structure that stores 64-bit hash values
-// and enables fast approximate matching based on Hamming distance (number of differing bits).
+// This module implements a 16-way branching tree structure that stores fixed-width hash
+// values and enables fast approximate matching based on Hamming distance (number of
+// differing bits).
 //
 // Key concepts:
-// - Each 64-bit hash is split into 16 chunks of 4 bits each
+// - Each hash is split into 4-bit chunks
 // - Each tree level uses one 4-bit chunk to decide which of 16 branches to follow
-// - The tree has a maximum depth of 16 levels (64 bits / 4 bits per level)
+// - The tree depth is a function of the fingerprint width (WORDS * 64 bits / 4 bits per level)
 // - During search, branches with similar bit patterns (within max_diff tolerance) are explored
 // - This allows finding "similar" hashes without comparing against every stored hash
 //
 // Time complexity:
-// - Insert: O(TREE_DEPTH) = O(16) = O(1)
+// - Insert: O(TREE_DEPTH) = O(1) for a fixed width
 // - Search: O(BRANCH_FACTOR^max_diff * TREE_DEPTH) in worst case, typically much better
 
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::io::{self, Read, Write};
+
 // Configuration constants for the hash tree structure
-// The tree breaks down a 64-bit hash into 4-bit chunks, creating a 16-way branching tree
+// The tree breaks down each hash word into 4-bit chunks, creating a 16-way branching tree
 const BRANCH_BITS: usize = 4;                          // Number of bits used per tree level
 const BRANCH_FACTOR: u8 = 1 << BRANCH_BITS;            // Number of branches per node (2^4 = 16)
-const TREE_DEPTH: usize = 64 / BRANCH_BITS;            // Total tree depth (64 bits / 4 bits per level = 16 levels)
 
 // Represents a branch in the hash tree - either empty or containing a child node
-enum HashTreeEntry<T> {
-    None,                        // Empty branch (no data in this path)
-    Node(Box<HashTree<T>>),      // Contains a subtree (boxed to avoid recursive type sizing issues)
+enum HashTreeEntry<T, const WORDS: usize> {
+    None,                                 // Empty branch (no data in this path)
+    Node(Box<HashTree<T, WORDS>>),        // Contains a subtree (boxed to avoid recursive type sizing issues)
 }
 
-impl<T> Default for HashTreeEntry<T> {
+impl<T, const WORDS: usize> Default for HashTreeEntry<T, WORDS> {
     fn default() -> Self {
         HashTreeEntry::None
     }
 }
 
-// Extracts the lowest 'bits' from a u64 value and returns (remaining_bits, extracted_bits)
-// This is used to progressively consume the hash value as we traverse down the tree
+// Extracts the lowest 'bits' bits from a wide hash value, returning (remaining, extracted).
+// Word 0 is the low-order word; bits extracted/shifted may carry across word boundaries.
+// This is used to progressively consume the hash value as we traverse down the tree.
 #[inline(always)]
-fn pop_bits(value: u64, bits: usize) -> (u64, u64) {
-    let mask = (1 << bits) - 1;              // Create a mask for the lowest 'bits' bits
-    (value >> bits, value & mask)            // Return (shifted value, extracted bits)
+fn pop_bits<const WORDS: usize>(value: [u64; WORDS], bits: usize) -> ([u64; WORDS], u64) {
+    let mask = (1u64 << bits) - 1;
+    let extracted = value[0] & mask;
+
+    let mut shifted = [0u64; WORDS];
+    for i in 0..WORDS {
+        let carry_in = if i + 1 < WORDS { (value[i + 1] & mask) << (64 - bits) } else { 0 };
+        shifted[i] = (value[i] >> bits) | carry_in;
+    }
+    (shifted, extracted)
 }
 
-// A 16-way branching tree for storing and searching hash values with Hamming distance tolerance
-// Each node has 16 branches (one for each possible 4-bit value) and optionally stores a value at leaf nodes
-pub struct HashTree<T> {
-    branches: [HashTreeEntry<T>; BRANCH_FACTOR as usize],  // 16 branches, one for each 4-bit pattern
-    value: Option<T>,                                       // Value stored at leaf nodes only
+// A 16-way branching tree for storing and searching `WORDS * 64`-bit hashes with Hamming
+// distance tolerance. `WORDS` defaults to 1 (a plain `u64` fingerprint) so existing callers
+// that only ever dealt with 64-bit hashes don't need to change.
+pub struct HashTree<T, const WORDS: usize = 1> {
+    branches: [HashTreeEntry<T, WORDS>; BRANCH_FACTOR as usize],  // 16 branches, one for each 4-bit pattern
+    value: Option<T>,                                             // Value stored at leaf nodes only
 }
 
-impl<T> HashTree<T> {
+impl<T, const WORDS: usize> HashTree<T, WORDS> {
+    // Total tree depth: one level per 4-bit chunk across all WORDS 64-bit words.
+    const TREE_DEPTH: usize = WORDS * 64 / BRANCH_BITS;
+
     // Creates an empty hash tree node with no branches or values
     pub fn new() -> Self {
         HashTree {
@@ -66,13 +82,13 @@ impl<T> HashTree<T> {
 
     // Searches for a hash value in the tree, allowing up to max_diff bit differences (Hamming distance)
     // Returns a reference to the stored value if a match is found within the tolerance
-    pub fn contains(&self, hash: u64, max_diff: u8) -> Option<&T> {
+    pub fn contains(&self, hash: [u64; WORDS], max_diff: u8) -> Option<&T> {
         self._contains(hash, max_diff, 0)
     }
 
     // Recursive implementation of contains that tracks tree depth and remaining allowed differences
-    fn _contains(&self, hash: u64, max_diff: u8, level: usize) -> Option<&T> {
-        let remaining_levels = TREE_DEPTH - level as usize;
+    fn _contains(&self, hash: [u64; WORDS], max_diff: u8, level: usize) -> Option<&T> {
+        let remaining_levels = Self::TREE_DEPTH - level;
 
         // Base case: reached a leaf level, return any stored value
         if remaining_levels == 0 {
@@ -104,11 +120,11 @@ impl<T> HashTree<T> {
     }
 
     // Recursive implementation to insert a value at the position determined by the hash
-    fn _add(&mut self, hash: u64, value: T, level: usize) {
+    fn _add(&mut self, hash: [u64; WORDS], value: T, level: usize) {
         let (rest, level_bits) = pop_bits(hash, BRANCH_BITS);
 
         // Base case: at the deepest level, create a leaf node with the value
-        if level == (TREE_DEPTH - 1) as usize {
+        if level == Self::TREE_DEPTH - 1 {
             self.branches[level_bits as usize] = HashTreeEntry::Node(HashTree::leaf(value).into());
             return;
         }
@@ -129,10 +145,41 @@ impl<T> HashTree<T> {
     }
 
     // Adds a value to the tree at the position determined by the hash
-    pub fn add(&mut self, hash: u64, value: T) {
+    pub fn add(&mut self, hash: [u64; WORDS], value: T) {
         self._add(hash, value, 0);
     }
 
+    /// Removes the value stored at `hash`, if any, pruning now-empty branches back up the
+    /// path so `len()` and subsequent searches don't see dead nodes.
+    pub fn remove(&mut self, hash: [u64; WORDS]) -> Option<T> {
+        self._remove(hash, 0).0
+    }
+
+    // Returns (the removed value, whether this node is now empty and can itself be pruned
+    // by its parent).
+    fn _remove(&mut self, hash: [u64; WORDS], level: usize) -> (Option<T>, bool) {
+        if level == Self::TREE_DEPTH {
+            return (self.value.take(), true);
+        }
+
+        let (rest, level_bits) = pop_bits(hash, BRANCH_BITS);
+        let idx = level_bits as usize;
+
+        let removed = match &mut self.branches[idx] {
+            HashTreeEntry::None => None,
+            HashTreeEntry::Node(node) => {
+                let (value, child_empty) = node._remove(rest, level + 1);
+                if child_empty {
+                    self.branches[idx] = HashTreeEntry::None;
+                }
+                value
+            }
+        };
+
+        let is_empty = self.value.is_none() && self.branches.iter().all(|b| matches!(b, HashTreeEntry::None));
+        (removed, is_empty)
+    }
+
     // Returns the total number of values stored in the tree
     pub fn len(&self) -> usize {
         let mut count = if self.value.is_some() { 1 } else { 0 };
@@ -145,4 +192,436 @@ impl<T> HashTree<T> {
 
         count
     }
+
+    /// Writes the tree to `w` in a compact depth-first encoding: each internal node is a
+    /// little-endian `u16` bitmask of which of its 16 branches are present, followed by
+    /// those present children in ascending branch order; each leaf is a one-byte "has
+    /// value" flag followed by the encoded value (via `write_value`) if set. There's no
+    /// `Serialize` bound on `T` here — the crate has no serde dependency to lean on, so the
+    /// caller supplies the value encoding directly instead.
+    pub fn serialize<W: Write>(
+        &self,
+        w: &mut W,
+        write_value: &mut impl FnMut(&T, &mut W) -> io::Result<()>,
+    ) -> io::Result<()> {
+        self._serialize(w, 0, write_value)
+    }
+
+    fn _serialize<W: Write>(
+        &self,
+        w: &mut W,
+        level: usize,
+        write_value: &mut impl FnMut(&T, &mut W) -> io::Result<()>,
+    ) -> io::Result<()> {
+        if level == Self::TREE_DEPTH {
+            match &self.value {
+                Some(value) => {
+                    w.write_all(&[1])?;
+                    write_value(value, w)?;
+                }
+                None => w.write_all(&[0])?,
+            }
+            return Ok(());
+        }
+
+        let mut mask: u16 = 0;
+        for i in 0..BRANCH_FACTOR {
+            if let HashTreeEntry::Node(_) = &self.branches[i as usize] {
+                mask |= 1 << i;
+            }
+        }
+        w.write_all(&mask.to_le_bytes())?;
+
+        for i in 0..BRANCH_FACTOR {
+            if let HashTreeEntry::Node(node) = &self.branches[i as usize] {
+                node._serialize(w, level + 1, write_value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a tree back from the encoding written by [`HashTree::serialize`]. `read_value`
+    /// must decode exactly what the paired `write_value` encoded, including byte count,
+    /// since there's no length prefix around each value.
+    pub fn deserialize<R: Read>(
+        r: &mut R,
+        read_value: &mut impl FnMut(&mut R) -> io::Result<T>,
+    ) -> io::Result<Self> {
+        Self::_deserialize(r, 0, read_value)
+    }
+
+    fn _deserialize<R: Read>(
+        r: &mut R,
+        level: usize,
+        read_value: &mut impl FnMut(&mut R) -> io::Result<T>,
+    ) -> io::Result<Self> {
+        if level == Self::TREE_DEPTH {
+            let mut flag = [0u8; 1];
+            r.read_exact(&mut flag)?;
+            let value = if flag[0] == 1 { Some(read_value(r)?) } else { None };
+            return Ok(HashTree {
+                branches: [const { HashTreeEntry::None }; BRANCH_FACTOR as usize],
+                value,
+            });
+        }
+
+        let mut mask_bytes = [0u8; 2];
+        r.read_exact(&mut mask_bytes)?;
+        let mask = u16::from_le_bytes(mask_bytes);
+
+        let mut tree = HashTree::new();
+        for i in 0..BRANCH_FACTOR {
+            if mask & (1 << i) != 0 {
+                let child = Self::_deserialize(r, level + 1, read_value)?;
+                tree.branches[i as usize] = HashTreeEntry::Node(child.into());
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Returns every stored value within `max_diff` Hamming distance of `hash`, each
+    /// paired with its exact distance, in no particular order.
+    ///
+    /// This is the same recursive descent as `contains`, but instead of returning on the
+    /// first match it keeps exploring every branch whose accumulated diff still fits the
+    /// budget, collecting every leaf it reaches. Use this over repeated `contains` calls
+    /// when a query may legitimately match more than one stored value, e.g. when grouping
+    /// near-duplicates.
+    pub fn matches(&self, hash: [u64; WORDS], max_diff: u8) -> Vec<(u8, &T)> {
+        let mut out = Vec::new();
+        self._matches(hash, max_diff, 0, 0, &mut out);
+        out
+    }
+
+    fn _matches<'a>(&'a self, hash: [u64; WORDS], max_diff: u8, diff: u8, level: usize, out: &mut Vec<(u8, &'a T)>) {
+        let remaining_levels = Self::TREE_DEPTH - level;
+
+        if remaining_levels == 0 {
+            if let Some(value) = self.value.as_ref() {
+                out.push((diff, value));
+            }
+            return;
+        }
+
+        let (rest, level_bits) = pop_bits(hash, BRANCH_BITS);
+
+        for i in 0..BRANCH_FACTOR {
+            let branch_diff = diff + (level_bits as u8 ^ i).count_ones() as u8;
+            if branch_diff <= max_diff {
+                if let HashTreeEntry::Node(node) = &self.branches[i as usize] {
+                    node._matches(rest, max_diff, branch_diff, level + 1, out);
+                }
+            }
+        }
+    }
+
+    /// Returns the `k` stored values with the smallest Hamming distance to `hash`,
+    /// ordered nearest first.
+    ///
+    /// Unlike `contains`, which stops at the first match within a fixed tolerance, this is
+    /// a best-first branch-and-bound search: a min-priority frontier (smallest accumulated
+    /// Hamming diff first) is expanded level by level, since the diff accumulated so far is
+    /// already a valid lower bound on the final distance (lower tree levels can only add to
+    /// it, never reduce it). A bounded max-heap of the `k` best complete results seen so far
+    /// is used to prune: once it holds `k` entries, any frontier entry whose accumulated
+    /// diff already exceeds the current k-th-best distance can never improve the result, so
+    /// the frontier pop loop can stop as soon as it sees one (every later pop only has an
+    /// equal or larger diff).
+    pub fn nearest(&self, hash: [u64; WORDS], k: usize) -> Vec<(u8, &T)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut frontier: BinaryHeap<Frontier<T, WORDS>> = BinaryHeap::new();
+        frontier.push(Frontier { diff: 0, level: 0, hash, node: self });
+
+        let mut best: BinaryHeap<BestMatch<T>> = BinaryHeap::with_capacity(k + 1);
+
+        while let Some(Frontier { diff, level, hash, node }) = frontier.pop() {
+            if best.len() == k && best.peek().is_some_and(|worst| diff > worst.0) {
+                // Every remaining frontier entry has diff >= this one, so none can help.
+                break;
+            }
+
+            if level == Self::TREE_DEPTH {
+                if let Some(value) = node.value.as_ref() {
+                    best.push(BestMatch(diff, value));
+                    if best.len() > k {
+                        best.pop();
+                    }
+                }
+                continue;
+            }
+
+            let (rest, level_bits) = pop_bits(hash, BRANCH_BITS);
+            for i in 0..BRANCH_FACTOR {
+                let branch_diff = diff + (level_bits as u8 ^ i).count_ones() as u8;
+                if best.len() == k && best.peek().is_some_and(|worst| branch_diff > worst.0) {
+                    continue;
+                }
+                if let HashTreeEntry::Node(child) = &node.branches[i as usize] {
+                    frontier.push(Frontier { diff: branch_diff, level: level + 1, hash: rest, node: child });
+                }
+            }
+        }
+
+        best.into_sorted_vec().into_iter().map(|BestMatch(d, v)| (d, v)).collect()
+    }
+}
+
+impl<U, const WORDS: usize> HashTree<Vec<U>, WORDS> {
+    /// Like `add`, but for a tree whose values are `Vec<U>`: on an exact-hash collision this
+    /// appends `value` to the existing leaf's vector instead of overwriting it, so distinct
+    /// inputs that happen to share a fingerprint are all retained rather than only the last
+    /// one written.
+    pub fn add_multi(&mut self, hash: [u64; WORDS], value: U) {
+        self._add_multi(hash, value, 0);
+    }
+
+    fn _add_multi(&mut self, hash: [u64; WORDS], value: U, level: usize) {
+        let (rest, level_bits) = pop_bits(hash, BRANCH_BITS);
+        let idx = level_bits as usize;
+
+        if level == Self::TREE_DEPTH - 1 {
+            match &mut self.branches[idx] {
+                HashTreeEntry::Node(node) => node.value.get_or_insert_with(Vec::new).push(value),
+                HashTreeEntry::None => {
+                    self.branches[idx] = HashTreeEntry::Node(HashTree::leaf(vec![value]).into());
+                }
+            }
+            return;
+        }
+
+        match self.branches[idx] {
+            HashTreeEntry::None => {
+                let mut node = HashTree::new();
+                node._add_multi(rest, value, level + 1);
+                self.branches[idx] = HashTreeEntry::Node(node.into());
+            }
+            HashTreeEntry::Node(ref mut node) => node._add_multi(rest, value, level + 1),
+        }
+    }
+}
+
+// A frontier entry in `HashTree::nearest`'s best-first search: `diff` is the exact Hamming
+// distance accumulated over the levels visited so far (a valid lower bound on the eventual
+// total, since deeper levels can only add to it). Ordered so a `BinaryHeap` pops the
+// smallest `diff` first, i.e. used as a min-priority queue.
+struct Frontier<'a, T, const WORDS: usize> {
+    diff: u8,
+    level: usize,
+    hash: [u64; WORDS],
+    node: &'a HashTree<T, WORDS>,
+}
+
+impl<'a, T, const WORDS: usize> PartialEq for Frontier<'a, T, WORDS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.diff == other.diff
+    }
+}
+impl<'a, T, const WORDS: usize> Eq for Frontier<'a, T, WORDS> {}
+impl<'a, T, const WORDS: usize> PartialOrd for Frontier<'a, T, WORDS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'a, T, const WORDS: usize> Ord for Frontier<'a, T, WORDS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.diff.cmp(&self.diff)
+    }
+}
+
+// Orders complete `nearest` results by distance, so a `BinaryHeap<BestMatch<T>>` can be used
+// as a bounded max-heap (a k-smallest selector): the current worst of the `k` best is at the top.
+struct BestMatch<'a, T>(u8, &'a T);
+
+impl<'a, T> PartialEq for BestMatch<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<'a, T> Eq for BestMatch<'a, T> {}
+impl<'a, T> PartialOrd for BestMatch<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'a, T> Ord for BestMatch<'a, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_narrow_tree_unchanged() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b1010], "a");
+        tree.add([0b1111], "b");
+
+        assert_eq!(tree.contains([0b1010], 0), Some(&"a"));
+        assert_eq!(tree.contains([0b1111], 0), Some(&"b"));
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn test_wide_tree_exact_match() {
+        let mut tree: HashTree<&str, 2> = HashTree::new();
+        let hash_a: [u64; 2] = [0x1, 0x0];
+        let hash_b: [u64; 2] = [0x0, 0x1];
+
+        tree.add(hash_a, "a");
+        tree.add(hash_b, "b");
+
+        assert_eq!(tree.contains(hash_a, 0), Some(&"a"));
+        assert_eq!(tree.contains(hash_b, 0), Some(&"b"));
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn test_wide_tree_tolerant_match_across_word_boundary() {
+        let mut tree: HashTree<&str, 2> = HashTree::new();
+        // Differs only in the top bit of the high word from the query below.
+        let stored: [u64; 2] = [0, 1 << 63];
+        tree.add(stored, "near");
+
+        let query: [u64; 2] = [0, 0];
+        assert_eq!(tree.contains(query, 0), None);
+        assert_eq!(tree.contains(query, 1), Some(&"near"));
+    }
+
+    #[test]
+    fn test_nearest_ranks_by_distance() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b0000], "exact");
+        tree.add([0b0001], "one_off");
+        tree.add([0b0011], "two_off");
+        tree.add([0b1111], "far");
+
+        let results = tree.nearest([0b0000], 3);
+        let distances: Vec<u8> = results.iter().map(|&(d, _)| d).collect();
+        let values: Vec<&str> = results.iter().map(|&(_, v)| *v).collect();
+
+        assert_eq!(distances, vec![0, 1, 2]);
+        assert_eq!(values, vec!["exact", "one_off", "two_off"]);
+    }
+
+    #[test]
+    fn test_nearest_k_larger_than_tree() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b0000], "a");
+        tree.add([0b1000], "b");
+
+        let results = tree.nearest([0b0000], 10);
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_nearest_zero_k_is_empty() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b0000], "a");
+        assert!(tree.nearest([0b0000], 0).is_empty());
+    }
+
+    #[test]
+    fn test_matches_finds_all_within_budget() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b0000], "exact");
+        tree.add([0b0001], "one_off");
+        tree.add([0b0010], "also_one_off");
+        tree.add([0b1111], "far");
+
+        let mut results = tree.matches([0b0000], 1);
+        results.sort_by_key(|&(d, v)| (d, v));
+
+        assert_eq!(
+            results,
+            vec![(0, "exact"), (1, "also_one_off"), (1, "one_off")]
+        );
+    }
+
+    #[test]
+    fn test_matches_empty_when_nothing_within_budget() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b1111], "far");
+        assert!(tree.matches([0b0000], 1).is_empty());
+    }
+
+    #[test]
+    fn test_serialize_deserialize_round_trip() {
+        let mut tree: HashTree<u32> = HashTree::new();
+        tree.add([0b1010], 10);
+        tree.add([0b1111], 15);
+        tree.add([0b0001], 1);
+
+        let mut buf = Vec::new();
+        tree.serialize(&mut buf, &mut |value, w| w.write_all(&value.to_le_bytes())).unwrap();
+
+        let restored = HashTree::<u32>::deserialize(&mut buf.as_slice(), &mut |r| {
+            let mut bytes = [0u8; 4];
+            r.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        })
+        .unwrap();
+
+        assert_eq!(restored.len(), 3);
+        assert_eq!(restored.contains([0b1010], 0), Some(&10));
+        assert_eq!(restored.contains([0b1111], 0), Some(&15));
+        assert_eq!(restored.contains([0b0001], 0), Some(&1));
+    }
+
+    #[test]
+    fn test_serialize_empty_tree_round_trips() {
+        let tree: HashTree<u32> = HashTree::new();
+        let mut buf = Vec::new();
+        tree.serialize(&mut buf, &mut |value, w| w.write_all(&value.to_le_bytes())).unwrap();
+
+        let restored = HashTree::<u32>::deserialize(&mut buf.as_slice(), &mut |r| {
+            let mut bytes = [0u8; 4];
+            r.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        })
+        .unwrap();
+        assert_eq!(restored.len(), 0);
+    }
+
+    #[test]
+    fn test_remove_clears_value_and_prunes_path() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b1010], "a");
+        tree.add([0b1111], "b");
+
+        assert_eq!(tree.remove([0b1010]), Some("a"));
+        assert_eq!(tree.contains([0b1010], 0), None);
+        assert_eq!(tree.len(), 1);
+        // The other entry is untouched.
+        assert_eq!(tree.contains([0b1111], 0), Some(&"b"));
+
+        // Removing the last entry prunes the tree back to empty.
+        assert_eq!(tree.remove([0b1111]), Some("b"));
+        assert_eq!(tree.len(), 0);
+    }
+
+    #[test]
+    fn test_remove_missing_hash_is_none() {
+        let mut tree: HashTree<&str> = HashTree::new();
+        tree.add([0b1010], "a");
+        assert_eq!(tree.remove([0b0000]), None);
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn test_add_multi_collects_colliding_values() {
+        let mut tree: HashTree<Vec<&str>> = HashTree::new();
+        tree.add_multi([0b1010], "a");
+        tree.add_multi([0b1010], "b");
+        tree.add_multi([0b0001], "c");
+
+        assert_eq!(tree.contains([0b1010], 0), Some(&vec!["a", "b"]));
+        assert_eq!(tree.contains([0b0001], 0), Some(&vec!["c"]));
+        assert_eq!(tree.len(), 2);
+    }
 }
@@ -94,6 +94,161 @@ where
     }
 }
 
+/// Like `sliding_window`, but instead of taking `window_size` contiguous elements, it takes
+/// every `gap`-th element across a wider span — a skip-gram. Spans `(window_size - 1) * gap
+/// + 1` elements and then slides forward by one, the same way `SlidingWindowIter` does, so
+/// consecutive spans overlap by all but one source element. With `gap > 1`, consecutive
+/// skip-grams themselves can share no element (`[a, c, e]` is followed by `[b, d, f]`).
+pub trait SkipWindowIterExt<T: Iterator> {
+    fn skip_window(self, window_size: usize, gap: usize) -> SkipWindowIter<T>;
+}
+
+impl<T: Iterator> SkipWindowIterExt<T> for T {
+    fn skip_window(self, window_size: usize, gap: usize) -> SkipWindowIter<T> {
+        SkipWindowIter::new(self, window_size, gap)
+    }
+}
+
+pub struct SkipWindowIter<T: Iterator> {
+    inp: T,
+    window: VecDeque<T::Item>,
+    window_size: usize,
+    gap: usize,
+    span: usize,
+}
+impl <T: Iterator> SkipWindowIter<T> {
+    pub fn new(inp: T, window_size: usize, gap: usize) -> Self {
+        let span = window_size.saturating_sub(1) * gap + 1;
+        Self { inp, window: VecDeque::with_capacity(span), window_size, gap, span }
+    }
+}
+impl<T: Iterator> Iterator for SkipWindowIter<T>
+where
+    T::Item: Clone
+{
+    type Item = Vec<T::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.window.len() < self.span {
+            match self.inp.next() {
+                Some(next) => self.window.push_back(next),
+                None => break,
+            }
+        }
+        if self.window.len() == self.span {
+            let result = (0..self.window_size).map(|i| self.window[i * self.gap].clone()).collect();
+            self.window.pop_front();
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+/// Buffers `window_size` contiguous elements. When the window first fills, it yields every
+/// `k`-element subset of the buffered items in lexicographic index order; after each later
+/// slide it yields only the subsets that contain the newest element, since every other
+/// subset of the window was already yielded against an earlier window.
+/// Combined with `skip_window`, this lets callers build shingle sets that tolerate word
+/// insertions and transpositions, since a subset survives even if elements between its
+/// members are reordered or duplicated.
+pub trait CombinationsIterExt<T: Iterator> {
+    fn combinations(self, window_size: usize, k: usize) -> CombinationsIter<T>;
+}
+
+impl<T: Iterator> CombinationsIterExt<T> for T {
+    fn combinations(self, window_size: usize, k: usize) -> CombinationsIter<T> {
+        CombinationsIter::new(self, window_size, k)
+    }
+}
+
+pub struct CombinationsIter<T: Iterator> {
+    inp: T,
+    window: VecDeque<T::Item>,
+    window_size: usize,
+    // Every k-subset of the window, emitted the first time the window fills.
+    initial_combos: Vec<Vec<usize>>,
+    // Only the k-subsets containing the newest element (index `window_size - 1`), emitted
+    // after every later slide: a subset missing the newest element was already emitted
+    // against the previous window, since sliding only drops the oldest element.
+    new_combos: Vec<Vec<usize>>,
+    slid: bool,
+    pending: usize,
+}
+impl <T: Iterator> CombinationsIter<T> {
+    pub fn new(inp: T, window_size: usize, k: usize) -> Self {
+        let initial_combos = index_combinations(window_size, k);
+        let new_combos = initial_combos
+            .iter()
+            .filter(|combo| combo.last() == Some(&(window_size.saturating_sub(1))))
+            .cloned()
+            .collect();
+        Self {
+            inp,
+            window: VecDeque::with_capacity(window_size),
+            window_size,
+            initial_combos,
+            new_combos,
+            slid: false,
+            pending: 0,
+        }
+    }
+
+    fn combos(&self) -> &[Vec<usize>] {
+        if self.slid { &self.new_combos } else { &self.initial_combos }
+    }
+}
+impl<T: Iterator> Iterator for CombinationsIter<T>
+where
+    T::Item: Clone
+{
+    type Item = Vec<T::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.window.len() == self.window_size && self.pending < self.combos().len() {
+                let combo = self.combos()[self.pending].clone();
+                self.pending += 1;
+                return Some(combo.iter().map(|&i| self.window[i].clone()).collect());
+            }
+            if self.window.len() == self.window_size {
+                self.window.pop_front();
+                self.slid = true;
+            }
+            match self.inp.next() {
+                Some(next) => {
+                    self.window.push_back(next);
+                    self.pending = 0;
+                }
+                None => return None,
+            }
+        }
+    }
+}
+
+// Generates every k-subset of `0..n`, as index lists in lexicographic order.
+fn index_combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k > n {
+        return Vec::new();
+    }
+    let mut result = Vec::new();
+    let mut combo: Vec<usize> = (0..k).collect();
+    loop {
+        result.push(combo.clone());
+        let next_index = (0..k).rev().find(|&i| combo[i] < n - k + i);
+        match next_index {
+            Some(i) => {
+                combo[i] += 1;
+                for j in (i + 1)..k {
+                    combo[j] = combo[j - 1] + 1;
+                }
+            }
+            None => break,
+        }
+    }
+    result
+}
+
 pub trait SequentialSlidingWindowIterExt<T: Iterator<Item = usize>> {
     fn sliding_sequential_window(self, window_size: usize) -> SequentialSlidingWindowIter<T>;
 }
@@ -191,6 +346,40 @@ mod tests {
         assert_eq!(windows.is_empty(), true);
     }
 
+    #[test]
+    fn test_skip_window() {
+        let data = b"abcdefgh";
+        let skips = data.iter().cloned().skip_window(3, 2).collect::<Vec<Vec<u8>>>();
+        assert_eq!(skips, vec![
+            vec![b'a', b'c', b'e'],
+            vec![b'b', b'd', b'f'],
+            vec![b'c', b'e', b'g'],
+            vec![b'd', b'f', b'h'],
+        ]);
+    }
+
+    #[test]
+    fn test_skip_window_too_short() {
+        let data = b"abc";
+        let skips = data.iter().cloned().skip_window(3, 2).collect::<Vec<Vec<u8>>>();
+        assert!(skips.is_empty());
+    }
+
+    #[test]
+    fn test_combinations() {
+        let data = b"abcd";
+        let combos = data.iter().cloned().combinations(3, 2).collect::<Vec<Vec<u8>>>();
+        // Window [a,b,c] emits every 2-subset; the slide to [b,c,d] only emits subsets
+        // containing the newest element `d`, since {b,c} was already emitted above.
+        assert_eq!(combos, vec![
+            vec![b'a', b'b'],
+            vec![b'a', b'c'],
+            vec![b'b', b'c'],
+            vec![b'b', b'd'],
+            vec![b'c', b'd'],
+        ]);
+    }
+
     #[test]
     fn test_graphemes() {
         let s = "a̐éö̲"; // a with combining